@@ -16,29 +16,10 @@ MOD_TEMPLATE = """
 
 pub(crate) const PARSEC_URL: &str = "{URL}";
 
-pub(crate) mod data;
 pub(crate) mod metallicity;
 pub(crate) mod masses;
 """
 
-DATA_TEMPLATE = """
-// This code is generated by generate_code.py, do not modify it manually.
-
-use lazy_static::lazy_static;
-
-use crate::data::ParsecData;
-
-lazy_static! {{
-    {static_data}
-}}
-
-lazy_static! {{
-    pub(crate) static ref DATA: [&'static ParsecData; {array_size}] = [
-        {access_array}
-    ];
-}}
-"""
-
 METALLICITY_TEMPLATE = """
 // This code is generated by generate_code.py, do not modify it manually.
 
@@ -175,19 +156,6 @@ def generate_mod_file():
     with open(TARGET_DIR + "mod.rs", 'w') as f:
         f.write(MOD_TEMPLATE.format(URL=URL))
 
-def generate_data_file(metallicities):
-    static_data = ""
-    access_array = ""
-    for index, metallicity in enumerate(metallicities):
-        variant_name = metallicity_variant_name(metallicity)
-        static_data += f"static {variant_name}_DATA: ParsecData = ParsecData::new({index});\n"
-        access_array += f"&{variant_name}_DATA,\n"
-
-    with open(TARGET_DIR + "data.rs", 'w') as f:
-        f.write(DATA_TEMPLATE.format(static_data=static_data, 
-                                     array_size=len(metallicities),
-                                     access_array=access_array))
-
 def mass_fraction_to_dex(mass_fraction):
     Z_sun = 0.0122
     return math.log10(mass_fraction / Z_sun)
@@ -278,7 +246,6 @@ def main():
 
     clean_target_dir()
     generate_mod_file()
-    generate_data_file(metallicities)
     generate_metallicity_file(metallicities, metallicity_to_archive_name)
     generate_masses_file(metallicities, metallicity_to_masses, metallicity_and_mass_to_filename)
 
@@ -0,0 +1,155 @@
+//! Contains the `BolometricCorrectionTable` struct, which maps effective temperature and surface gravity to a bolometric correction.
+
+use std::{fs, path::Path};
+
+use crate::error::ParsecAccessError;
+
+/// A table of bolometric corrections BC = M_bol - M_band for one photometric band.
+///
+/// The table is a regular grid in effective temperature and log10 of the surface gravity in cgs units, which is bilinearly interpolated.
+/// Values outside of the grid are clamped to its edges.
+///
+/// # Example
+/// ```
+/// use parsec_access::bolometric_correction::BolometricCorrectionTable;
+///
+/// let csv = "teff,logg,bc\n5000,4.0,-0.3\n5000,5.0,-0.2\n6000,4.0,-0.1\n6000,5.0,0.0";
+/// let table = BolometricCorrectionTable::from_csv_str(csv).unwrap();
+/// assert!((table.interpolate(5500., 4.5) - -0.15).abs() < 1e-8);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BolometricCorrectionTable {
+    temperatures_in_kelvin: Vec<f64>,
+    log_gs: Vec<f64>,
+    /// The corrections, indexed by temperature index first and log g index second.
+    corrections: Vec<Vec<f64>>,
+}
+
+impl BolometricCorrectionTable {
+    /// Creates a table from rows of effective temperature in K, log10 of the surface gravity in cgs units, and bolometric correction.
+    /// The rows may appear in any order, but need to cover every combination of the occurring temperatures and surface gravities.
+    pub fn new(rows: &[(f64, f64, f64)]) -> Result<Self, ParsecAccessError> {
+        let temperatures_in_kelvin = sorted_unique(rows.iter().map(|row| row.0));
+        let log_gs = sorted_unique(rows.iter().map(|row| row.1));
+        if temperatures_in_kelvin.is_empty() || log_gs.is_empty() {
+            let message = "Bolometric correction table is empty".to_string();
+            return Err(ParsecAccessError::InvalidData(message));
+        }
+
+        let mut corrections = vec![vec![f64::NAN; log_gs.len()]; temperatures_in_kelvin.len()];
+        for &(temperature, log_g, correction) in rows {
+            let temperature_index = temperatures_in_kelvin.partition_point(|&t| t < temperature);
+            let log_g_index = log_gs.partition_point(|&g| g < log_g);
+            corrections[temperature_index][log_g_index] = correction;
+        }
+        for (temperature_index, row) in corrections.iter().enumerate() {
+            if let Some(log_g_index) = row.iter().position(|correction| correction.is_nan()) {
+                let message = format!(
+                    "Bolometric correction table has no entry for T_eff = {} K and log g = {}",
+                    temperatures_in_kelvin[temperature_index], log_gs[log_g_index]
+                );
+                return Err(ParsecAccessError::InvalidData(message));
+            }
+        }
+
+        Ok(Self {
+            temperatures_in_kelvin,
+            log_gs,
+            corrections,
+        })
+    }
+
+    /// Parses a table from comma separated text with the columns effective temperature in K, log g in cgs units, and bolometric correction.
+    /// Empty lines, lines starting with '#' and a header line are skipped.
+    pub fn from_csv_str(csv: &str) -> Result<Self, ParsecAccessError> {
+        let mut rows = Vec::new();
+        for (line_number, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let values: Result<Vec<f64>, _> = line
+                .split(',')
+                .map(|entry| entry.trim().parse::<f64>())
+                .collect();
+            match values {
+                Ok(values) if values.len() == 3 => rows.push((values[0], values[1], values[2])),
+                Err(_) if rows.is_empty() => continue,
+                _ => {
+                    let message = format!(
+                        "Could not parse line {} of bolometric correction table: '{line}'",
+                        line_number + 1
+                    );
+                    return Err(ParsecAccessError::InvalidData(message));
+                }
+            }
+        }
+        Self::new(&rows)
+    }
+
+    /// Reads a table from a comma separated file, see from_csv_str() for the expected format.
+    pub fn from_csv_file(path: &Path) -> Result<Self, ParsecAccessError> {
+        let csv = fs::read_to_string(path).map_err(ParsecAccessError::Io)?;
+        Self::from_csv_str(&csv)
+    }
+
+    /// Returns the bilinearly interpolated bolometric correction for the given effective temperature in K and log10 of the surface gravity in cgs units.
+    pub fn interpolate(&self, temperature_in_kelvin: f64, log_g: f64) -> f64 {
+        let (t_lower, t_upper, t_weight) =
+            bracket(&self.temperatures_in_kelvin, temperature_in_kelvin);
+        let (g_lower, g_upper, g_weight) = bracket(&self.log_gs, log_g);
+        let lower = self.corrections[t_lower][g_lower]
+            + g_weight * (self.corrections[t_lower][g_upper] - self.corrections[t_lower][g_lower]);
+        let upper = self.corrections[t_upper][g_lower]
+            + g_weight * (self.corrections[t_upper][g_upper] - self.corrections[t_upper][g_lower]);
+        lower + t_weight * (upper - lower)
+    }
+}
+
+fn sorted_unique(values: impl Iterator<Item = f64>) -> Vec<f64> {
+    let mut values: Vec<f64> = values.collect();
+    values.sort_by(f64::total_cmp);
+    values.dedup();
+    values
+}
+
+/// Returns the indices enclosing the value and the fraction of the way from the lower to the upper one, clamped to the ends of the list.
+pub(crate) fn bracket(list: &[f64], value: f64) -> (usize, usize, f64) {
+    let upper = list.partition_point(|&entry| entry < value);
+    if upper == 0 {
+        return (0, 0, 0.);
+    }
+    if upper >= list.len() {
+        return (list.len() - 1, list.len() - 1, 0.);
+    }
+    let lower = upper - 1;
+    let weight = (value - list[lower]) / (list[upper] - list[lower]);
+    (lower, upper, weight)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CSV: &str = "# teff,logg,bc\n6000,4.0,-0.1\n5000,4.0,-0.3\n5000,5.0,-0.2\n6000,5.0,0.0";
+
+    #[test]
+    fn grid_points_are_reproduced() {
+        let table = BolometricCorrectionTable::from_csv_str(CSV).expect("table should parse");
+        assert!((table.interpolate(5000., 5.0) - -0.2).abs() < 1e-8);
+        assert!((table.interpolate(6000., 4.0) - -0.1).abs() < 1e-8);
+    }
+
+    #[test]
+    fn values_outside_of_grid_are_clamped() {
+        let table = BolometricCorrectionTable::from_csv_str(CSV).expect("table should parse");
+        assert!((table.interpolate(10000., 6.0) - 0.0).abs() < 1e-8);
+        assert!((table.interpolate(1000., 1.0) - -0.3).abs() < 1e-8);
+    }
+
+    #[test]
+    fn incomplete_grid_is_rejected() {
+        let result = BolometricCorrectionTable::new(&[(5000., 4.0, -0.3), (6000., 5.0, 0.0)]);
+        assert!(matches!(result, Err(ParsecAccessError::InvalidData(_))));
+    }
+}
@@ -0,0 +1,500 @@
+//! Contains the `ParsecConfig` struct, which bundles the options that control how the PARSEC data is obtained.
+
+use std::{
+    ops::Range,
+    path::PathBuf,
+    sync::{PoisonError, RwLock},
+    time::Duration,
+};
+
+use astro_units::mass::solar_mass;
+use lazy_static::lazy_static;
+use uom::si::f64::Mass;
+
+use crate::{
+    access::{
+        masses::{FILENAMES, MASSES},
+        metallicity::{
+            METALLICITIES_IN_DEX, METALLICITIES_IN_MASS_FRACTION, METALLICITY_ARCHIVES,
+            METALLICITY_NAMES,
+        },
+        PARSEC_URL,
+    },
+    constants::SOLAR_METALLICITY_IN_MASS_FRACTION,
+    data::is_any_loaded,
+    error::ParsecAccessError,
+    file::validate_prepared_data,
+};
+
+#[cfg(feature = "test-data")]
+use crate::test_data;
+
+const DEFAULT_AUTHOR: &str = "the_comamba";
+
+lazy_static! {
+    static ref CONFIG: RwLock<ParsecConfig> = RwLock::new(ParsecConfig::default());
+}
+
+/// The set of PARSEC evolutionary tracks the data is taken from.
+/// It determines the metallicity grid, the mass grid, and where and under which names the data files are found, so every getter follows the selected set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TrackSet {
+    /// The PARSEC version 1.2S tracks with solar-scaled abundances, as published at CAF09_V1.2S_M36_LT.
+    #[default]
+    V1_2S,
+    /// A small synthetic set of tracks that is embedded into the binary, so that tests can run offline and deterministically.
+    /// It covers two metallicities and three masses, and is only available with the `test-data` feature.
+    #[cfg(feature = "test-data")]
+    TestData,
+}
+
+impl TrackSet {
+    /// The track sets that are available with the enabled features.
+    pub(crate) const ALL: &'static [TrackSet] = &[
+        TrackSet::V1_2S,
+        #[cfg(feature = "test-data")]
+        TrackSet::TestData,
+    ];
+
+    /// Returns the url the archives of the track set are downloaded from, which is empty for embedded tracks.
+    pub fn url(&self) -> &'static str {
+        match self {
+            TrackSet::V1_2S => PARSEC_URL,
+            #[cfg(feature = "test-data")]
+            TrackSet::TestData => "",
+        }
+    }
+
+    pub(crate) fn metallicities_in_mass_fraction(&self) -> &'static [f64] {
+        match self {
+            TrackSet::V1_2S => &METALLICITIES_IN_MASS_FRACTION,
+            #[cfg(feature = "test-data")]
+            TrackSet::TestData => &test_data::METALLICITIES_IN_MASS_FRACTION,
+        }
+    }
+
+    pub(crate) fn metallicities_in_dex(&self) -> &'static [f64] {
+        match self {
+            TrackSet::V1_2S => &METALLICITIES_IN_DEX,
+            #[cfg(feature = "test-data")]
+            TrackSet::TestData => &test_data::METALLICITIES_IN_DEX,
+        }
+    }
+
+    pub(crate) fn metallicity_names(&self) -> &'static [&'static str] {
+        match self {
+            TrackSet::V1_2S => &METALLICITY_NAMES,
+            #[cfg(feature = "test-data")]
+            TrackSet::TestData => &test_data::METALLICITY_NAMES,
+        }
+    }
+
+    pub(crate) fn archives(&self) -> &'static [&'static str] {
+        match self {
+            TrackSet::V1_2S => &METALLICITY_ARCHIVES,
+            #[cfg(feature = "test-data")]
+            TrackSet::TestData => &test_data::METALLICITY_ARCHIVES,
+        }
+    }
+
+    pub(crate) fn filenames(&self) -> &'static [&'static [&'static str]] {
+        match self {
+            TrackSet::V1_2S => &FILENAMES,
+            #[cfg(feature = "test-data")]
+            TrackSet::TestData => &test_data::FILENAMES,
+        }
+    }
+
+    pub(crate) fn masses(&self) -> &'static [&'static [f64]] {
+        match self {
+            TrackSet::V1_2S => &MASSES,
+            #[cfg(feature = "test-data")]
+            TrackSet::TestData => &test_data::MASSES,
+        }
+    }
+
+    /// Returns the content of the data files, ordered like filenames(), if the tracks are embedded instead of downloaded.
+    pub(crate) fn embedded_files(&self) -> Option<&'static [&'static [&'static str]]> {
+        match self {
+            TrackSet::V1_2S => None,
+            #[cfg(feature = "test-data")]
+            TrackSet::TestData => Some(&test_data::FILE_CONTENTS),
+        }
+    }
+}
+
+/// The configuration used when downloading and reading the PARSEC data.
+/// It is constructed in a builder-like fashion and activated by passing it to init().
+///
+/// # Example
+/// ```
+/// use parsec_access::config::ParsecConfig;
+/// use std::time::Duration;
+///
+/// let config = ParsecConfig::new()
+///     .retries(3)
+///     .timeout(Duration::from_secs(600));
+/// assert_eq!(config.get_retries(), 3);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ParsecConfig {
+    data_dir: Option<PathBuf>,
+    author: Option<String>,
+    app_name: Option<String>,
+    url: Option<String>,
+    track_set: TrackSet,
+    mass_range: Option<(Mass, Mass)>,
+    strict: bool,
+    pre_extracted: bool,
+    clean_up_old_data: bool,
+    threads: Option<usize>,
+    offline: bool,
+    retries: u32,
+    timeout: Option<Duration>,
+    expected_archive_sizes: Option<Vec<u64>>,
+    solar_metallicity: Option<f64>,
+}
+
+impl ParsecConfig {
+    /// Creates a configuration with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the directory the PARSEC data is stored in.
+    /// If none is provided, a directory in the standard config location of the operating system is used.
+    pub fn data_dir(mut self, data_dir: impl Into<PathBuf>) -> Self {
+        self.data_dir = Some(data_dir.into());
+        self
+    }
+
+    /// Sets the author under which the data directory is placed in the standard config location.
+    /// If none is provided, the author of this crate is used.
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Sets the name of the data directory in the standard config location.
+    /// If none is provided, the name and version of this crate are used, so every version of the crate keeps its own copy of the data.
+    /// A host application can choose a name of its own to namespace its data, and to keep it across updates of this crate.
+    pub fn app_name(mut self, app_name: impl Into<String>) -> Self {
+        self.app_name = Some(app_name.into());
+        self
+    }
+
+    /// Sets the url the PARSEC archives are downloaded from.
+    /// The archive names are appended to this url, so it should end with a slash.
+    /// If none is provided, the url of the selected track set is used.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Selects the set of PARSEC tracks that is downloaded and read.
+    /// The grid is fixed once any data is loaded, so the track set needs to be selected before that.
+    pub fn track_set(mut self, track_set: TrackSet) -> Self {
+        self.track_set = track_set;
+        self
+    }
+
+    /// Restricts the loaded trajectories to initial masses between min and max, inclusively.
+    /// Masses outside of this range are absent from the grid, which saves memory and loading time.
+    /// The grid is fixed once any data is loaded, so the mass range needs to be set before that.
+    pub fn mass_range(mut self, min: Mass, max: Mass) -> Self {
+        self.mass_range = Some((min, max));
+        self
+    }
+
+    /// Some PARSEC files contain repeated or out-of-order ages at phase transitions.
+    /// By default, such entries are skipped while reading. In strict mode, they are reported as an error instead.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Declares that the data directory has been populated in advance, for example by an administrator.
+    /// In this mode the data directory is treated as read-only: nothing is downloaded, trimmed or cleaned up.
+    /// The expected data files are only checked for existence, and missing files are reported as an error.
+    pub fn pre_extracted(mut self, pre_extracted: bool) -> Self {
+        self.pre_extracted = pre_extracted;
+        self
+    }
+
+    /// Allows deleting the data directories of other versions of this crate from the standard config location.
+    /// This is off by default, because several versions may share the location on purpose.
+    /// It has no effect if a custom data directory or app name is set.
+    pub fn clean_up_old_data(mut self, clean_up_old_data: bool) -> Self {
+        self.clean_up_old_data = clean_up_old_data;
+        self
+    }
+
+    /// Limits the number of threads used to parse the data files of a metallicity.
+    /// If none is provided, the global rayon thread pool is used.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// In offline mode, missing data is reported as an error instead of being downloaded.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Sets how often a failed download is retried before giving up.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the maximum duration a single download may take.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the expected size in bytes of the archive of each metallicity, ordered like the metallicity indices.
+    /// A downloaded archive of a different size is reported as an error before it is unpacked, which catches truncated transfers cheaply.
+    /// If none are provided, the sizes reported by the server are used, if any.
+    pub fn expected_archive_sizes(mut self, sizes: impl Into<Vec<u64>>) -> Self {
+        self.expected_archive_sizes = Some(sizes.into());
+        self
+    }
+
+    /// Sets the solar metallicity Z_sun in units of mass fraction that is used as the reference when converting to and from [Fe/H] in dex.
+    /// If none is provided, constants::SOLAR_METALLICITY_IN_MASS_FRACTION = 0.0122 is used.
+    /// Other abundance scales are in common use, for example Z_sun = 0.0134 by Asplund et al. (2009), and shift all dex values noticeably.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::config::{init, ParsecConfig};
+    /// use parsec_access::getters::{get_closest_metallicity_index_from_fe_dex, get_metallicities_in_mass_fractions};
+    ///
+    /// init(ParsecConfig::new().solar_metallicity(0.0134)).unwrap();
+    /// let index = get_closest_metallicity_index_from_fe_dex(0.);
+    /// assert_eq!(get_metallicities_in_mass_fractions()[index], 0.014);
+    /// ```
+    pub fn solar_metallicity(mut self, z_sun: f64) -> Self {
+        self.solar_metallicity = Some(z_sun);
+        self
+    }
+
+    /// Returns the custom data directory, if one has been set.
+    pub fn get_data_dir(&self) -> Option<&PathBuf> {
+        self.data_dir.as_ref()
+    }
+
+    /// Returns the author under which the data directory is placed in the standard config location.
+    pub fn get_author(&self) -> &str {
+        self.author.as_deref().unwrap_or(DEFAULT_AUTHOR)
+    }
+
+    /// Returns the custom name of the data directory, if one has been set.
+    pub fn get_app_name(&self) -> Option<&str> {
+        self.app_name.as_deref()
+    }
+
+    /// Returns the url the PARSEC archives are downloaded from.
+    pub fn get_url(&self) -> &str {
+        self.url.as_deref().unwrap_or(self.track_set.url())
+    }
+
+    /// Returns the selected set of PARSEC tracks.
+    pub fn get_track_set(&self) -> TrackSet {
+        self.track_set
+    }
+
+    /// Returns the solar metallicity Z_sun in units of mass fraction that is used as the reference for [Fe/H].
+    pub fn get_solar_metallicity(&self) -> f64 {
+        self.solar_metallicity
+            .unwrap_or(SOLAR_METALLICITY_IN_MASS_FRACTION)
+    }
+
+    /// Returns the range of initial masses that are loaded, if one has been set.
+    pub fn get_mass_range(&self) -> Option<(Mass, Mass)> {
+        self.mass_range
+    }
+
+    /// Returns the range of indices into the full mass grid of a metallicity that are loaded.
+    pub(crate) fn mass_indices(&self, metallicity_index: usize) -> Range<usize> {
+        let masses = self.track_set.masses()[metallicity_index];
+        match self.mass_range {
+            Some((min, max)) => {
+                let min = min.get::<solar_mass>();
+                let max = max.get::<solar_mass>();
+                let start = masses.partition_point(|&mass| mass < min);
+                let end = masses.partition_point(|&mass| mass <= max);
+                start..end.max(start)
+            }
+            None => 0..masses.len(),
+        }
+    }
+
+    /// Returns whether strict mode is active.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns whether the data directory is treated as pre-extracted and read-only.
+    pub fn is_pre_extracted(&self) -> bool {
+        self.pre_extracted
+    }
+
+    /// Returns whether the data directories of other versions of this crate are deleted.
+    pub fn is_cleaning_up_old_data(&self) -> bool {
+        self.clean_up_old_data
+    }
+
+    /// Returns the number of threads used for parsing, if it has been limited.
+    pub fn get_threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    /// Returns whether offline mode is active.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Returns how often a failed download is retried.
+    pub fn get_retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Returns the maximum duration a single download may take, if one has been set.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns the expected size in bytes of the archive of a metallicity, if one has been set.
+    pub fn get_expected_archive_size(&self, metallicity_index: usize) -> Option<u64> {
+        self.expected_archive_sizes
+            .as_ref()?
+            .get(metallicity_index)
+            .copied()
+    }
+}
+
+/// Activates the provided configuration.
+///
+/// The data is loaded lazily, so this should be called before the data is first accessed.
+/// Metallicities that are already loaded are not affected.
+/// Once any data is loaded, the index grids have to stay the same, so an error is returned without changing the active configuration if the new one selects a different track set or mass range.
+///
+/// # Example
+/// ```
+/// use parsec_access::config::{init, ParsecConfig};
+///
+/// init(ParsecConfig::new().retries(2)).unwrap();
+/// ```
+pub fn init(config: ParsecConfig) -> Result<(), ParsecAccessError> {
+    let mut current = CONFIG.write().unwrap_or_else(PoisonError::into_inner);
+    let changes_grid =
+        config.track_set != current.track_set || config.mass_range != current.mass_range;
+    if changes_grid && is_any_loaded() {
+        return Err(ParsecAccessError::InvalidInput(
+            "The track set and mass range cannot be changed once data is loaded".to_string(),
+        ));
+    }
+    *current = config;
+    Ok(())
+}
+
+/// Activates a configuration for data that has been placed in the provided directory in advance, for example by a distribution package.
+///
+/// The directory is expected to contain the extracted archive of every metallicity, as the crate would have downloaded it.
+/// It is validated right away, and an error is returned without changing the active configuration if any data file is missing.
+/// Afterwards, the data directory is treated as read-only and nothing is ever downloaded.
+///
+/// # Example
+/// ```
+/// use parsec_access::config::use_prepared_data;
+///
+/// assert!(use_prepared_data("this_folder_does_not_exist").is_err());
+/// ```
+pub fn use_prepared_data(data_dir: impl Into<PathBuf>) -> Result<(), ParsecAccessError> {
+    let data_dir = data_dir.into();
+    let config = ParsecConfig::new()
+        .data_dir(&data_dir)
+        .pre_extracted(true)
+        .offline(true);
+    validate_prepared_data(&data_dir, &config)?;
+    init(config)
+}
+
+pub(crate) fn current_config() -> ParsecConfig {
+    CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Returns the track set of the active configuration, without cloning the whole configuration.
+pub(crate) fn current_track_set() -> TrackSet {
+    CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get_track_set()
+}
+
+/// Returns the range of indices into the full mass grid of a metallicity that are loaded with the active configuration, without cloning the whole configuration.
+pub(crate) fn current_mass_indices(metallicity_index: usize) -> Range<usize> {
+    CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .mass_indices(metallicity_index)
+}
+
+/// Returns the solar metallicity of the active configuration, without cloning the whole configuration.
+pub(crate) fn current_solar_metallicity() -> f64 {
+    CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get_solar_metallicity()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_config_downloads_from_parsec_url() {
+        let config = ParsecConfig::new();
+        assert_eq!(config.get_url(), PARSEC_URL);
+        assert_eq!(config.get_track_set(), TrackSet::V1_2S);
+        assert!(config.get_data_dir().is_none());
+        assert_eq!(config.get_author(), DEFAULT_AUTHOR);
+        assert!(config.get_app_name().is_none());
+        assert!(!config.is_offline());
+        assert!(!config.is_cleaning_up_old_data());
+    }
+
+    #[test]
+    fn custom_author_and_app_name_are_kept() {
+        let config = ParsecConfig::new().author("someone").app_name("my_app");
+        assert_eq!(config.get_author(), "someone");
+        assert_eq!(config.get_app_name(), Some("my_app"));
+    }
+
+    #[test]
+    fn solar_metallicity_defaults_to_constant() {
+        assert_eq!(
+            ParsecConfig::new().get_solar_metallicity(),
+            SOLAR_METALLICITY_IN_MASS_FRACTION
+        );
+        let config = ParsecConfig::new().solar_metallicity(0.0134);
+        assert_eq!(config.get_solar_metallicity(), 0.0134);
+    }
+
+    #[test]
+    fn mass_range_restricts_mass_indices() {
+        let min = Mass::new::<solar_mass>(0.8);
+        let max = Mass::new::<solar_mass>(1.2);
+        let config = ParsecConfig::new().mass_range(min, max);
+        let masses = &MASSES[0][config.mass_indices(0)];
+        assert!(!masses.is_empty());
+        assert!(masses.iter().all(|&mass| (0.8..=1.2).contains(&mass)));
+        assert_eq!(ParsecConfig::new().mass_indices(0).len(), MASSES[0].len());
+    }
+}
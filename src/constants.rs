@@ -0,0 +1,31 @@
+//! Solar reference values and physical constants used throughout the crate.
+//!
+//! Use these in your own code to stay consistent with the numbers the crate uses internally.
+
+/// The metallicity of the sun in units of mass fraction Z.
+/// This is the reference value for converting mass fractions to [Fe/H] in dex.
+pub const SOLAR_METALLICITY_IN_MASS_FRACTION: f64 = 0.0122;
+
+/// The mass of the sun in kilograms, matching the solar_mass unit of the astro_units crate.
+pub const SOLAR_MASS_IN_KG: f64 = 1.988416e30;
+
+/// The nominal radius of the sun in kilometers, matching the solar_radius unit of the astro_units crate.
+pub const SOLAR_RADIUS_IN_KM: f64 = 695_700.;
+
+/// The nominal luminosity of the sun in Watts, as defined by the IAU.
+pub const SOLAR_LUMINOSITY_IN_WATT: f64 = 3.828e26;
+
+/// The effective temperature of the sun in Kelvin.
+pub const SOLAR_TEMPERATURE_IN_KELVIN: f64 = 5772.;
+
+/// The absolute bolometric magnitude of the sun.
+pub const SOLAR_BOLOMETRIC_MAGNITUDE: f64 = 4.74;
+
+/// Newton's gravitational constant G in m^3 / (kg s^2).
+pub const GRAVITATIONAL_CONSTANT_IN_SI: f64 = 6.6743e-11;
+
+/// The speed of light in vacuum in meters per second.
+pub const SPEED_OF_LIGHT_IN_M_PER_S: f64 = 299_792_458.;
+
+/// The Stefan-Boltzmann constant in W / (m^2 K^4).
+pub const STEFAN_BOLTZMANN_CONSTANT_IN_SI: f64 = 5.670374419e-8;
@@ -1,42 +1,135 @@
 //! Contains the `ParsecData` struct, which holds the PARSEC data for a given metallicity.
 
-use std::ops::Index;
+use std::{
+    borrow::Cow,
+    ops::Index,
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+use lazy_static::lazy_static;
 
 use crate::{
+    config::{current_solar_metallicity, TrackSet},
+    error::ParsecAccessError,
     file::{get_data_dir, read_data_files},
     trajectory::Trajectory,
 };
 
+/// The data that is handed out while a metallicity could not be read.
+static INVALID_DATA: ParsecData = ParsecData {
+    metallicity_in_mass_fraction: 0.0,
+    masses_in_solar: Cow::Borrowed(&[]),
+    data: Vec::new(),
+};
+
+lazy_static! {
+    /// The data of each metallicity, which is read on first access and kept for the rest of the program.
+    /// A slot stays empty while reading fails, so that the next access tries again.
+    /// There is a slot for every metallicity of the largest track set, because the track set may still change until data is loaded.
+    static ref LOADED_DATA: Vec<DataSlot> = {
+        let max_num_metallicities = TrackSet::ALL
+            .iter()
+            .map(|track_set| track_set.metallicities_in_mass_fraction().len())
+            .max()
+            .unwrap_or(0);
+        (0..max_num_metallicities).map(|_| DataSlot::default()).collect()
+    };
+}
+
+#[derive(Default)]
+struct DataSlot {
+    data: OnceLock<ParsecData>,
+    /// Held while reading, so that concurrent first accesses read the files only once.
+    reading: Mutex<()>,
+}
+
 /// The data struct holding the PARSEC data for a given metallicity.
-/// This struct cannot be created directly, but can only be read and accessed through the crate api.
-/// If you know the metallicity index, the contained trajectories can be accessed via the index operator.
+/// This struct cannot be created directly, but can only be read and accessed through the crate api, or parsed from in-memory sources with from_readers().
+/// If you know the mass index, the contained trajectories can be accessed via the index operator.
+/// The trajectories are ordered by increasing initial mass, so the trajectory at a mass index always belongs to the mass at the same index of get_masses_in_solar().
 pub struct ParsecData {
     /// The metallicity of the data in units of mass fraction Z.
     pub metallicity_in_mass_fraction: f64,
+    /// The initial masses of the loaded trajectories, which may be a subset of all masses if a mass range is configured.
+    pub(crate) masses_in_solar: Cow<'static, [f64]>,
     pub(crate) data: Vec<Trajectory>,
 }
 
 impl ParsecData {
-    pub(crate) fn new(metallicity_index: usize) -> ParsecData {
-        let data_dir = match get_data_dir() {
-            Ok(dir) => dir,
+    fn read(metallicity_index: usize) -> Result<ParsecData, ParsecAccessError> {
+        let data_dir = get_data_dir()?;
+        read_data_files(metallicity_index, &data_dir)
+    }
+
+    /// Returns the metallicity of the data in dex for the element iron, [Fe/H] = log10(Z / Z_sun).
+    /// Z_sun is the solar metallicity set in the configuration, so the result matches get_metallicities_relative_to_solar().
+    ///
+    /// See the documentation of get_metallicities_in_fe_dex() for the assumptions going into the unit conversion.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_data, get_metallicities_relative_to_solar, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let data = get_data(1);
+    /// assert!((data.metallicity_in_fe_dex() - get_metallicities_relative_to_solar()[1]).abs() < 1e-8);
+    /// ```
+    pub fn metallicity_in_fe_dex(&self) -> f64 {
+        (self.metallicity_in_mass_fraction / current_solar_metallicity()).log10()
+    }
+
+    /// Returns the total number of lines across all trajectories of the metallicity.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_data, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let data = get_data(1);
+    /// assert_eq!(data.total_lines(), data.lines_per_mass().iter().sum::<usize>());
+    /// ```
+    pub fn total_lines(&self) -> usize {
+        self.data.iter().map(Trajectory::len).sum()
+    }
+
+    /// Returns the number of lines of each trajectory of the metallicity, ordered like the mass indices.
+    pub fn lines_per_mass(&self) -> Vec<usize> {
+        self.data.iter().map(Trajectory::len).collect()
+    }
+
+    /// Returns the data of the metallicity, reading it first if this is the first access.
+    /// If reading fails, the error is printed and invalid data is returned.
+    pub(crate) fn loaded(metallicity_index: usize) -> &'static ParsecData {
+        match ParsecData::load(metallicity_index) {
+            Ok(data) => data,
             Err(err) => {
                 eprintln!(
-                    "Error getting data directory for metallicity index {metallicity_index}: {err}"
+                    "Error reading PARSEC data for metallicity index {metallicity_index}: {err}"
                 );
-                return ParsecData::default();
-            }
-        };
-        let result = read_data_files(metallicity_index, &data_dir);
-        match result {
-            Ok(data) => data,
-            Err(err) => {
-                eprintln!("Error reading PARSEC data for metallicity index {metallicity_index} from data dir '{}': {err}", data_dir.display());
-                ParsecData::default()
+                &INVALID_DATA
             }
         }
     }
 
+    /// Reads the data of the metallicity if it is not loaded yet, reporting a failure as an error.
+    pub(crate) fn load(metallicity_index: usize) -> Result<&'static ParsecData, ParsecAccessError> {
+        let slot = &LOADED_DATA[metallicity_index];
+        if let Some(data) = slot.data.get() {
+            return Ok(data);
+        }
+        let _reading = slot.reading.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(data) = slot.data.get() {
+            return Ok(data);
+        }
+        let data = ParsecData::read(metallicity_index)?;
+        Ok(slot.data.get_or_init(|| data))
+    }
+
+    /// Returns whether data has been read into memory for the metallicity, without reading it.
+    pub(crate) fn is_loaded(metallicity_index: usize) -> bool {
+        LOADED_DATA[metallicity_index].data.get().is_some()
+    }
+
     pub(crate) fn is_valid(&self) -> bool {
         let has_valid_metallicity = self.metallicity_in_mass_fraction > 0.0;
         if !has_valid_metallicity {
@@ -54,10 +147,16 @@ impl ParsecData {
     }
 }
 
+/// Returns whether data has been read into memory for any metallicity, without reading it.
+pub(crate) fn is_any_loaded() -> bool {
+    LOADED_DATA.iter().any(|slot| slot.data.get().is_some())
+}
+
 impl Default for ParsecData {
     fn default() -> Self {
         Self {
             metallicity_in_mass_fraction: 0.0,
+            masses_in_solar: Cow::Borrowed(&[]),
             data: Vec::new(),
         }
     }
@@ -81,7 +180,7 @@ mod test {
         time::second,
     };
 
-    use crate::line::ParsecLine;
+    use crate::{constants::SOLAR_METALLICITY_IN_MASS_FRACTION, line::ParsecLine};
 
     use super::*;
 
@@ -93,24 +192,50 @@ mod test {
 
     #[test]
     fn empty_data_is_invalid() {
-        let mut data = ParsecData::default();
-        data.metallicity_in_mass_fraction = 0.05;
+        let data = ParsecData {
+            metallicity_in_mass_fraction: 0.05,
+            ..Default::default()
+        };
         assert!(!data.is_valid());
     }
 
     #[test]
     fn data_with_empty_trajectory_is_invalid() {
-        let mut data = ParsecData::default();
-        data.metallicity_in_mass_fraction = 0.05;
+        let mut data = ParsecData {
+            metallicity_in_mass_fraction: 0.05,
+            ..Default::default()
+        };
         let valid_line = ParsecLine {
             mass: Mass::new::<kilogram>(1.),
             age: Time::new::<second>(1.),
             luminosity_in_solar: 1.,
             temperature: ThermodynamicTemperature::new::<kelvin>(1.),
             radius: Length::new::<meter>(1.),
+            #[cfg(feature = "raw_columns")]
+            raw_columns: Vec::new(),
         };
         data.data.push(Trajectory::new(vec![valid_line]));
         data.data.push(Trajectory::new(vec![]));
         assert!(!data.is_valid());
     }
+
+    #[test]
+    fn line_counts_sum_over_trajectories() {
+        let mut data = ParsecData::default();
+        let line = ParsecLine::sun_like(1e9);
+        data.data
+            .push(Trajectory::new(vec![line.clone(), line.clone()]));
+        data.data.push(Trajectory::new(vec![line]));
+        assert_eq!(data.lines_per_mass(), vec![2, 1]);
+        assert_eq!(data.total_lines(), 3);
+    }
+
+    #[test]
+    fn solar_metallicity_is_zero_dex() {
+        let data = ParsecData {
+            metallicity_in_mass_fraction: SOLAR_METALLICITY_IN_MASS_FRACTION,
+            ..Default::default()
+        };
+        assert!(data.metallicity_in_fe_dex().abs() < 1e-8);
+    }
 }
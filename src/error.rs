@@ -13,8 +13,14 @@ pub enum ParsecAccessError {
     Glob(glob::GlobError),
     /// An error occurred while trying to parse a glob pattern.
     GlobPattern(glob::PatternError),
+    /// The data is present, but does not make physical sense.
+    InvalidData(String),
+    /// The arguments passed to a function are inconsistent.
+    InvalidInput(String),
     /// An I/O error occurred.
     Io(std::io::Error),
+    /// A queried value lies outside of the range covered by the data, so the result would have to be extrapolated.
+    OutOfRange(String),
     /// Some other error occurred.
     Other(String),
 }
@@ -26,7 +32,10 @@ impl fmt::Display for ParsecAccessError {
             ParsecAccessError::DataNotAvailable(data) => write!(f, "Data {} not available", data),
             ParsecAccessError::Glob(err) => write!(f, "Glob error: {}", err),
             ParsecAccessError::GlobPattern(err) => write!(f, "Glob pattern error: {}", err),
+            ParsecAccessError::InvalidData(message) => write!(f, "Invalid data: {}", message),
+            ParsecAccessError::InvalidInput(message) => write!(f, "Invalid input: {}", message),
             ParsecAccessError::Io(err) => write!(f, "I/O error: {}", err),
+            ParsecAccessError::OutOfRange(message) => write!(f, "Out of range: {}", message),
             ParsecAccessError::Other(err) => write!(f, "Other error: {}", err),
         }
     }
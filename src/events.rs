@@ -0,0 +1,74 @@
+//! Lifecycle events emitted while the PARSEC data is downloaded and read.
+
+use std::sync::{PoisonError, RwLock};
+
+use lazy_static::lazy_static;
+
+/// The type of a function that is notified about load events.
+pub type EventHandler = Box<dyn Fn(LoadEvent) + Send + Sync>;
+
+lazy_static! {
+    static ref EVENT_HANDLER: RwLock<Option<EventHandler>> = RwLock::new(None);
+}
+
+/// An event that occurs while the PARSEC data for a metallicity is obtained.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadEvent {
+    /// The archive for the metallicity is about to be downloaded.
+    DownloadStarted {
+        /// The index of the metallicity.
+        metallicity_index: usize,
+    },
+    /// The archive for the metallicity has been downloaded and unpacked.
+    DownloadFinished {
+        /// The index of the metallicity.
+        metallicity_index: usize,
+    },
+    /// The data files for the metallicity are about to be parsed.
+    ParsingStarted {
+        /// The index of the metallicity.
+        metallicity_index: usize,
+    },
+    /// The data for the metallicity has been parsed and is ready to use.
+    MetallicityLoaded {
+        /// The index of the metallicity.
+        metallicity_index: usize,
+    },
+}
+
+/// Registers a function that is called whenever a load event occurs.
+/// This replaces any previously registered handler. By default, no handler is registered.
+///
+/// The handler may be called from several threads at once, and must not register or clear handlers itself.
+///
+/// # Example
+/// ```
+/// use parsec_access::events::{set_event_handler, LoadEvent};
+///
+/// set_event_handler(Box::new(|event| {
+///     if let LoadEvent::MetallicityLoaded { metallicity_index } = event {
+///         println!("Metallicity {metallicity_index} is ready.");
+///     }
+/// }));
+/// ```
+pub fn set_event_handler(handler: EventHandler) {
+    let mut current = EVENT_HANDLER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    *current = Some(handler);
+}
+
+/// Removes the registered event handler, if there is one.
+pub fn clear_event_handler() {
+    let mut current = EVENT_HANDLER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    *current = None;
+}
+
+pub(crate) fn emit(event: LoadEvent) {
+    let handler = EVENT_HANDLER.read().unwrap_or_else(PoisonError::into_inner);
+    if let Some(handler) = handler.as_ref() {
+        handler(event);
+    }
+}
@@ -2,114 +2,553 @@ use etcetera::{choose_app_strategy, AppStrategy, AppStrategyArgs};
 use flate2::read::GzDecoder;
 use glob::glob;
 use rayon::prelude::*;
+use std::borrow::Cow;
 use std::fs;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, Write};
 use std::path::{Path, PathBuf};
 use tar::Archive;
 
-use crate::access::masses::FILENAMES;
-use crate::access::metallicity::{
-    METALLICITIES_IN_MASS_FRACTION, METALLICITY_ARCHIVES, METALLICITY_NAMES,
-};
-use crate::access::PARSEC_URL;
+use crate::config::{current_config, current_track_set, ParsecConfig};
 use crate::data::ParsecData;
 use crate::error::ParsecAccessError;
-use crate::line::ParsecLine;
+use crate::events::{emit, LoadEvent};
+use crate::line::{ColumnLayout, ParsecLine};
 use crate::trajectory::Trajectory;
 use crate::{PACKAGE_NAME, PACKAGE_VERSION};
 
-impl ParsecData {}
+impl ParsecData {
+    /// Parses the data of a metallicity from in-memory sources instead of the cached data files, without touching the disk.
+    /// Each reader provides the content of one trajectory file, and belongs to the initial mass at the same position of masses_in_solar.
+    ///
+    /// The result is independent of the data loaded through the getters. The strict setting of the current configuration applies.
+    /// Returns an InvalidInput error if the number of masses and readers differ, or if the masses are not strictly increasing.
+    /// Returns an error as well if a trajectory cannot be parsed, or if the resulting data is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::data::ParsecData;
+    /// use astro_units::mass::solar_mass;
+    ///
+    /// let track = "MODELL MASS AGE LOG_L LOG_TE LOG_R\n0 1.0 1e6 0.0 3.76 10.84\n1 1.0 1e9 0.1 3.75 10.86\n";
+    /// let data = ParsecData::from_readers(0.01, vec![1.0], vec![track.as_bytes()]).unwrap();
+    /// assert_eq!(data.total_lines(), 2);
+    /// assert!((data[0].initial_mass.get::<solar_mass>() - 1.).abs() < 1e-8);
+    /// ```
+    pub fn from_readers<R: BufRead>(
+        metallicity_in_mass_fraction: f64,
+        masses_in_solar: Vec<f64>,
+        readers: impl IntoIterator<Item = R>,
+    ) -> Result<ParsecData, ParsecAccessError> {
+        let readers: Vec<R> = readers.into_iter().collect();
+        if readers.len() != masses_in_solar.len() {
+            let message = format!(
+                "{} masses were provided for {} trajectories.",
+                masses_in_solar.len(),
+                readers.len()
+            );
+            return Err(ParsecAccessError::InvalidInput(message));
+        }
+        if !masses_in_solar.windows(2).all(|pair| pair[0] < pair[1]) {
+            let message = "The provided masses are not strictly increasing.".to_string();
+            return Err(ParsecAccessError::InvalidInput(message));
+        }
+        let strict = current_config().is_strict();
+        let data = readers
+            .into_iter()
+            .enumerate()
+            .map(|(mass_index, reader)| {
+                read_trajectory(reader, &format!("trajectory {mass_index}"), strict)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let parsec_data = ParsecData {
+            metallicity_in_mass_fraction,
+            masses_in_solar: Cow::Owned(masses_in_solar),
+            data,
+        };
+        if parsec_data.is_valid() {
+            Ok(parsec_data)
+        } else {
+            let message = "The provided PARSEC data is empty.".to_string();
+            Err(ParsecAccessError::DataNotAvailable(message))
+        }
+    }
+}
+
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
 
-fn download(metallicity_index: usize) -> Result<(), ParsecAccessError> {
+fn download_with_retries(metallicity_index: usize) -> Result<(), ParsecAccessError> {
+    let config = current_config();
+    let mut attempt = 0;
+    loop {
+        let result = download(metallicity_index, &config);
+        if result.is_err() {
+            remove_partial_download(metallicity_index);
+        }
+        match result {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < config.get_retries() => {
+                attempt += 1;
+                eprintln!(
+                    "Download attempt {attempt} of {} failed: {err}",
+                    config.get_retries() + 1
+                );
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn download(metallicity_index: usize, config: &ParsecConfig) -> Result<(), ParsecAccessError> {
     let data_dir = get_data_dir()?;
-    let data_dir = data_dir
-        .to_str()
-        .ok_or(ParsecAccessError::Io(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "Could not convert data dir to string",
-        )))?;
-    let archive_name = METALLICITY_ARCHIVES[metallicity_index];
+    let archive_name = config.get_track_set().archives()[metallicity_index];
     println!(
         "Downloading PARSEC data archive {} to {}",
-        archive_name, data_dir
+        archive_name,
+        data_dir.display()
+    );
+    emit(LoadEvent::DownloadStarted { metallicity_index });
+    let target = config.get_url().to_string() + archive_name;
+    let archive_path = data_dir.join(format!("{archive_name}.part"));
+    let reported_size = fetch_archive(&target, &archive_path, config)?;
+    let expected_size = config
+        .get_expected_archive_size(metallicity_index)
+        .or(reported_size);
+    verify_archive_size(&archive_path, expected_size, &target)?;
+
+    let result = unpack_archive(&archive_path, &data_dir, &target);
+    // Once unpacked, the archive is no longer needed. If unpacking failed, it is corrupt and needs to be fetched anew.
+    let _ = fs::remove_file(&archive_path);
+    result?;
+    emit(LoadEvent::DownloadFinished { metallicity_index });
+    Ok(())
+}
+
+fn unpack_archive(
+    archive_path: &Path,
+    data_dir: &Path,
+    target: &str,
+) -> Result<(), ParsecAccessError> {
+    let mut file = File::open(archive_path).map_err(ParsecAccessError::Io)?;
+    let mut magic_bytes = [0u8; 2];
+    let is_gzip = file.read_exact(&mut magic_bytes).is_ok() && magic_bytes == GZIP_MAGIC_BYTES;
+    if !is_gzip {
+        let message = format!("The content of {target} is not a gzip archive");
+        return Err(ParsecAccessError::InvalidData(message));
+    }
+    file.rewind().map_err(ParsecAccessError::Io)?;
+    let mut archive = Archive::new(GzDecoder::new(BufReader::new(file)));
+    archive.unpack(data_dir).map_err(ParsecAccessError::Io)
+}
+
+/// Unpacks an archive that has been placed in the data directory by hand, for example after copying it from another machine.
+/// The archive itself is left in place, because it belongs to the user.
+fn unpack_local_archive(
+    metallicity_index: usize,
+    archive_path: &Path,
+    data_dir: &Path,
+) -> Result<(), ParsecAccessError> {
+    println!(
+        "Unpacking local PARSEC data archive {}",
+        archive_path.display()
     );
-    let target = PARSEC_URL.to_string() + archive_name;
-    let mut response = ureq::get(target)
+    let result = unpack_archive(archive_path, data_dir, &archive_path.to_string_lossy());
+    if result.is_err() {
+        remove_partial_download(metallicity_index);
+    }
+    result
+}
+
+fn create_agent(config: &ParsecConfig) -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(config.get_timeout())
+        .build()
+        .into()
+}
+
+/// Asks the server for the size of the archive of a metallicity in bytes, without downloading it.
+pub(crate) fn archive_size(metallicity_index: usize) -> Result<u64, ParsecAccessError> {
+    let config = current_config();
+    let archive_name = config.get_track_set().archives()[metallicity_index];
+    let target = config.get_url().to_string() + archive_name;
+    let response = create_agent(&config)
+        .head(&target)
         .call()
         .map_err(ParsecAccessError::Connection)?;
-    let mut body = response.body_mut().as_reader();
-    let gz_decoder = GzDecoder::new(&mut body);
-    let mut archive = Archive::new(gz_decoder);
-    archive.unpack(data_dir).map_err(ParsecAccessError::Io)?;
-    Ok(())
+    response
+        .headers()
+        .get("Content-Length")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .ok_or_else(|| {
+            let message = format!("The server did not report the size of {target}");
+            ParsecAccessError::DataNotAvailable(message)
+        })
+}
+
+/// Compares the size of the downloaded archive with the expected size, if one is known, to catch truncated transfers before unpacking.
+/// An archive that is too short is kept, so that the next attempt can resume it. An archive that is too long is corrupt and removed.
+fn verify_archive_size(
+    archive_path: &Path,
+    expected_size: Option<u64>,
+    target: &str,
+) -> Result<(), ParsecAccessError> {
+    let Some(expected_size) = expected_size else {
+        return Ok(());
+    };
+    let size = fs::metadata(archive_path)
+        .map_err(ParsecAccessError::Io)?
+        .len();
+    if size == expected_size {
+        return Ok(());
+    }
+    if size > expected_size {
+        let _ = fs::remove_file(archive_path);
+    }
+    let message =
+        format!("Received {size} bytes of {target}, but the archive has {expected_size} bytes");
+    Err(ParsecAccessError::InvalidData(message))
+}
+
+/// Downloads the archive to the provided path, and returns its total size in bytes if the server reported it.
+/// If a previous attempt left part of the archive there, only the missing bytes are requested via an HTTP range request.
+/// Servers that do not support range requests answer with the full archive, which then replaces the partial one.
+fn fetch_archive(
+    target: &str,
+    archive_path: &Path,
+    config: &ParsecConfig,
+) -> Result<Option<u64>, ParsecAccessError> {
+    let agent = create_agent(config);
+    let downloaded_bytes = fs::metadata(archive_path).map_or(0, |metadata| metadata.len());
+    let mut request = agent.get(target);
+    if downloaded_bytes > 0 {
+        println!("Resuming download after {downloaded_bytes} bytes");
+        request = request.header("Range", format!("bytes={downloaded_bytes}-"));
+    }
+    let mut response = match request.call() {
+        Ok(response) => response,
+        // The requested range starts at the end of the archive, so it is already complete.
+        Err(ureq::Error::StatusCode(416)) if downloaded_bytes > 0 => return Ok(None),
+        Err(err) => return Err(ParsecAccessError::Connection(err)),
+    };
+    let content_type = response
+        .headers()
+        .get("Content-Type")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if content_type.starts_with("text/") {
+        let message = format!(
+            "The server returned {content_type} instead of an archive for {target}, which probably is an error page"
+        );
+        return Err(ParsecAccessError::InvalidData(message));
+    }
+    let is_resumed = response.status().as_u16() == 206;
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let total_size = if is_resumed {
+        // A partial response reports the total size after the slash, as in "bytes 100-199/200".
+        header("Content-Range").and_then(|range| range.rsplit('/').next()?.trim().parse().ok())
+    } else {
+        header("Content-Length").and_then(|length| length.trim().parse().ok())
+    };
+    let mut file = if is_resumed {
+        fs::OpenOptions::new().append(true).open(archive_path)
+    } else {
+        File::create(archive_path)
+    }
+    .map_err(ParsecAccessError::Io)?;
+    std::io::copy(&mut response.body_mut().as_reader(), &mut file)
+        .map_err(ParsecAccessError::Io)?;
+    Ok(total_size)
 }
 
-fn read_trajectory_file(file_path: PathBuf) -> Result<Trajectory, ParsecAccessError> {
-    let file = match File::open(&file_path).map_err(ParsecAccessError::Io) {
+/// Removes whatever has been extracted of an archive, so that the next attempt downloads it again instead of trusting broken files.
+fn remove_partial_download(metallicity_index: usize) {
+    let Ok(data_dir) = get_data_dir() else {
+        return;
+    };
+    let path = data_dir.join(archive_dir_name(metallicity_index));
+    if path.exists() {
+        println!("Removing partially downloaded data: {}", path.display());
+        if let Err(err) = fs::remove_dir_all(&path) {
+            eprintln!(
+                "Could not remove partially downloaded data '{}': {err}",
+                path.display()
+            );
+        }
+    }
+}
+
+fn read_trajectory_file(
+    metallicity_index: usize,
+    file_path: PathBuf,
+    strict: bool,
+) -> Result<Trajectory, ParsecAccessError> {
+    let file = match File::open(&file_path) {
         Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            // The archive was extracted, but does not contain a file it is expected to contain, so upstream packaging has probably changed.
+            let filename = file_path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            let message = format!(
+                "file '{filename}' of metallicity {} (expected at '{}')",
+                current_track_set().metallicity_names()[metallicity_index],
+                file_path.display()
+            );
+            return Err(ParsecAccessError::DataNotAvailable(message));
+        }
         Err(err) => {
             let message = format!(
                 "Could not open trajectory file '{}': {err}",
                 file_path.display()
             );
-            let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, message);
+            let io_err = std::io::Error::new(err.kind(), message);
             return Err(ParsecAccessError::Io(io_err));
         }
     };
-    let reader = BufReader::new(file);
+    let source = format!("trajectory file '{}'", file_path.display());
+    read_trajectory(BufReader::new(file), &source, strict)
+}
+
+/// Parses the lines of a trajectory from the reader, where source describes their origin in messages.
+fn read_trajectory<R: BufRead>(
+    reader: R,
+    source: &str,
+    strict: bool,
+) -> Result<Trajectory, ParsecAccessError> {
     let mut lines = vec![];
+    let mut removed = 0;
+    let mut layout = None;
     for line in reader.lines() {
         let line = line.map_err(ParsecAccessError::Io)?;
-        if !is_header(&line) {
-            let line = ParsecLine::read(line)?;
-            lines.push(line);
+        if is_header(&line) {
+            layout = Some(layout_from_header(&line, source));
+            continue;
+        }
+        let layout = layout.get_or_insert_with(|| {
+            eprintln!("No header found in {source}, assuming the default column layout");
+            ColumnLayout::default()
+        });
+        if !push_if_monotonic(&mut lines, ParsecLine::read_with_layout(line, layout)?) {
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        let message = format!("{removed} repeated or out-of-order ages in {source}");
+        if strict {
+            return Err(ParsecAccessError::InvalidData(message));
         }
+        eprintln!("Skipping {message}");
     }
     Ok(Trajectory::new(lines))
 }
 
-fn ensure_data_files(metallicity_index: usize) -> Result<(), ParsecAccessError> {
+/// Locates the required columns by their labels in the header, falling back to the default layout if any label is missing.
+fn layout_from_header(header: &str, source: &str) -> ColumnLayout {
+    ColumnLayout::from_header(header).unwrap_or_else(|| {
+        eprintln!(
+            "The header of {source} lacks some of the expected column labels, assuming the default column layout"
+        );
+        ColumnLayout::default()
+    })
+}
+
+/// Appends the line if its age is strictly larger than the age of the last kept line, and returns whether it was kept.
+fn push_if_monotonic(kept: &mut Vec<ParsecLine>, line: ParsecLine) -> bool {
+    match kept.last() {
+        Some(previous) if line.age <= previous.age => false,
+        _ => {
+            kept.push(line);
+            true
+        }
+    }
+}
+
+/// Returns whether accessing the metallicity would trigger a download, because its data is not cached yet.
+pub(crate) fn is_download_pending(metallicity_index: usize) -> bool {
+    let config = current_config();
+    if config.is_pre_extracted()
+        || config.is_offline()
+        || config.get_track_set().embedded_files().is_some()
+    {
+        return false;
+    }
+    match get_data_dir() {
+        Ok(data_dir) => {
+            let archive_name = config.get_track_set().archives()[metallicity_index];
+            !data_dir.join(archive_dir_name(metallicity_index)).exists()
+                && !data_dir.join(archive_name).is_file()
+        }
+        Err(_) => true,
+    }
+}
+
+/// Creates the data directory including its parents, because not every system creates the standard config location up front.
+fn create_data_dir(data_dir: &Path) -> Result<(), ParsecAccessError> {
+    fs::create_dir_all(data_dir).map_err(|err| {
+        let message = format!(
+            "Could not create the data directory '{}': {err}",
+            data_dir.display()
+        );
+        ParsecAccessError::Io(std::io::Error::new(err.kind(), message))
+    })
+}
+
+pub(crate) fn ensure_data_files(metallicity_index: usize) -> Result<(), ParsecAccessError> {
+    let config = current_config();
+    if config.get_track_set().embedded_files().is_some() {
+        return Ok(());
+    }
     let data_dir = get_data_dir()?;
-    let dirname = METALLICITY_ARCHIVES[metallicity_index].replace(".tar.gz", "");
+    let dirname = archive_dir_name(metallicity_index);
     let path = data_dir.join(PathBuf::from(dirname));
+    if config.is_pre_extracted() {
+        return validate_data_files(metallicity_index, &path, &config);
+    }
     if !path.exists() {
-        download(metallicity_index)?;
-        reduce_persisted_data(metallicity_index)?;
+        let local_archive = data_dir.join(config.get_track_set().archives()[metallicity_index]);
+        if local_archive.is_file() {
+            unpack_local_archive(metallicity_index, &local_archive, &data_dir)?;
+        } else if config.is_offline() {
+            let message = format!(
+                "{} (offline mode is active, so it is not downloaded)",
+                path.display()
+            );
+            return Err(ParsecAccessError::DataNotAvailable(message));
+        } else {
+            create_data_dir(&data_dir)?;
+            download_with_retries(metallicity_index)?;
+        }
+        if let Err(err) = reduce_persisted_data(metallicity_index) {
+            remove_partial_download(metallicity_index);
+            return Err(err);
+        }
+    }
+    if config.is_cleaning_up_old_data()
+        && config.get_data_dir().is_none()
+        && config.get_app_name().is_none()
+    {
+        clean_up_old_data_dirs()?;
+    }
+    Ok(())
+}
+
+/// Copies the data files of every metallicity into the destination, in the same layout as the data directory.
+/// Missing data is downloaded and reduced first. Embedded tracks are written from memory.
+pub(crate) fn export_data_files(dest: &Path) -> Result<(), ParsecAccessError> {
+    let data_dir = get_data_dir()?;
+    let config = current_config();
+    for metallicity_index in 0..config.get_track_set().archives().len() {
+        ensure_data_files(metallicity_index)?;
+        let dirname = archive_dir_name_for(&config, metallicity_index);
+        let source_dir = data_dir.join(&dirname);
+        let dest_dir = dest.join(&dirname);
+        fs::create_dir_all(&dest_dir).map_err(ParsecAccessError::Io)?;
+        let filenames = &config.get_track_set().filenames()[metallicity_index];
+        let embedded_files = config.get_track_set().embedded_files();
+        for mass_index in config.mass_indices(metallicity_index) {
+            let filename = filenames[mass_index];
+            match embedded_files {
+                Some(contents) => fs::write(
+                    dest_dir.join(filename),
+                    contents[metallicity_index][mass_index],
+                )
+                .map_err(ParsecAccessError::Io)?,
+                None => {
+                    fs::copy(source_dir.join(filename), dest_dir.join(filename))
+                        .map_err(ParsecAccessError::Io)?;
+                }
+            }
+        }
     }
-    clean_up_old_data_dirs()?;
     Ok(())
 }
 
+/// Checks that the data directory of the configuration holds the data files of every metallicity.
+pub(crate) fn validate_prepared_data(
+    data_dir: &Path,
+    config: &ParsecConfig,
+) -> Result<(), ParsecAccessError> {
+    for metallicity_index in 0..config.get_track_set().archives().len() {
+        let folder_path = data_dir.join(archive_dir_name_for(config, metallicity_index));
+        validate_data_files(metallicity_index, &folder_path, config)?;
+    }
+    Ok(())
+}
+
+fn validate_data_files(
+    metallicity_index: usize,
+    folder_path: &Path,
+    config: &ParsecConfig,
+) -> Result<(), ParsecAccessError> {
+    let filenames = &config.get_track_set().filenames()[metallicity_index];
+    for filename in &filenames[config.mass_indices(metallicity_index)] {
+        let file_path = folder_path.join(filename);
+        if !file_path.is_file() {
+            let message = format!(
+                "{} (the data directory is pre-extracted, so it is not downloaded)",
+                file_path.display()
+            );
+            return Err(ParsecAccessError::DataNotAvailable(message));
+        }
+    }
+    Ok(())
+}
+
+/// Deletes the data directories of other versions of this crate, which only happens if the configuration opts into it.
 fn clean_up_old_data_dirs() -> Result<(), ParsecAccessError> {
+    let old_dirs = find_old_data_dirs()?;
+    if old_dirs.is_empty() {
+        return Ok(());
+    }
+    println!("\nRemoving {} old data directories:", old_dirs.len());
+    for path in old_dirs.iter() {
+        println!("  {}", path.display());
+    }
+    println!();
+    for path in old_dirs {
+        fs::remove_dir_all(&path).map_err(ParsecAccessError::Io)?;
+    }
+    Ok(())
+}
+
+fn find_old_data_dirs() -> Result<Vec<PathBuf>, ParsecAccessError> {
     let data_dir = get_data_dir()?;
     let data_dir_str = data_dir
         .to_str()
-        .ok_or(ParsecAccessError::Io(std::io::Error::new(
-            std::io::ErrorKind::Other,
+        .ok_or(ParsecAccessError::Io(std::io::Error::other(
             "Could not convert data dir to string",
         )))?;
     let parts: Vec<&str> = data_dir_str.split('_').collect();
     let data_dir_glob = parts[..parts.len() - 1].join("_") + "_*";
     let current_folder = current_app_name();
 
+    let mut old_dirs = vec![];
     let entries = glob(&data_dir_glob).map_err(ParsecAccessError::GlobPattern)?;
     for entry in entries {
         let path = entry.map_err(ParsecAccessError::Glob)?;
         if !path.to_str().unwrap_or_default().contains(&current_folder) {
-            println!("\nRemoving old data directory: {:?}\n", path);
-            fs::remove_dir_all(&path).map_err(ParsecAccessError::Io)?;
+            old_dirs.push(path);
         }
     }
-    Ok(())
+    Ok(old_dirs)
 }
 
 fn reduce_persisted_data(metallicity_index: usize) -> Result<(), ParsecAccessError> {
     let data_dir = get_data_dir()?;
-    let data_dir_name = METALLICITY_ARCHIVES[metallicity_index].replace(".tar.gz", "");
+    let data_dir_name = archive_dir_name(metallicity_index);
     let folder_path = data_dir.join(PathBuf::from(data_dir_name));
     delete_unnecessary_files(&folder_path)?;
-    trim_files(&folder_path, metallicity_index)?;
+    if !cfg!(feature = "raw_columns") {
+        trim_files(&folder_path, metallicity_index)?;
+    }
     Ok(())
 }
 
@@ -142,7 +581,7 @@ fn trim_files(folder_path: &Path, metallicity_index: usize) -> Result<(), Parsec
     println!("Trimming files in {}", folder_path.to_string_lossy());
 
     let required_line_number = ParsecLine::LARGEST_REQUIRED_INDEX + 1;
-    let filepaths = FILENAMES[metallicity_index];
+    let filepaths = current_config().get_track_set().filenames()[metallicity_index];
     for filepath in filepaths {
         let filepath = folder_path.join(filepath);
         trim_file(&filepath, required_line_number)?;
@@ -151,24 +590,58 @@ fn trim_files(folder_path: &Path, metallicity_index: usize) -> Result<(), Parsec
     Ok(())
 }
 
-fn trim_file(file_path: &PathBuf, required_line_number: usize) -> Result<(), ParsecAccessError> {
-    let file = File::open(file_path).map_err(ParsecAccessError::Io)?;
-    let reader = BufReader::new(file);
-    let mut lines: Vec<String> = Vec::new();
+/// Trims the file line by line into a temporary file, which then atomically replaces the original.
+/// If anything goes wrong, the original file is left untouched.
+fn trim_file(file_path: &Path, required_line_number: usize) -> Result<(), ParsecAccessError> {
+    let mut temp_path = file_path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    let result = write_trimmed_file(file_path, &temp_path, required_line_number)
+        .and_then(|()| fs::rename(&temp_path, file_path).map_err(ParsecAccessError::Io));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_trimmed_file(
+    file_path: &Path,
+    temp_path: &Path,
+    required_line_number: usize,
+) -> Result<(), ParsecAccessError> {
+    let reader = BufReader::new(File::open(file_path).map_err(ParsecAccessError::Io)?);
+    let mut writer = BufWriter::new(File::create(temp_path).map_err(ParsecAccessError::Io)?);
 
     for line in reader.lines() {
         let line = line.map_err(ParsecAccessError::Io)?;
-        let columns: Vec<&str> = line.split_whitespace().collect();
-        let trimmed_columns = columns
-            .into_iter()
-            .take(required_line_number)
-            .collect::<Vec<&str>>()
-            .join("\t");
-        lines.push(trimmed_columns);
+        let trimmed_columns = &line[..end_of_column(&line, required_line_number)];
+        writeln!(writer, "{trimmed_columns}").map_err(ParsecAccessError::Io)?;
     }
 
-    fs::write(file_path, lines.join("\n")).map_err(ParsecAccessError::Io)?;
-    Ok(())
+    writer.flush().map_err(ParsecAccessError::Io)
+}
+
+/// Returns the byte offset at which the given number of whitespace-separated columns of the line ends.
+/// Slicing the line there keeps the kept columns byte for byte, including their formatting and separators.
+fn end_of_column(line: &str, column_number: usize) -> usize {
+    if column_number == 0 {
+        return 0;
+    }
+    let mut columns = 0;
+    let mut is_in_column = false;
+    for (offset, c) in line.char_indices() {
+        if c.is_whitespace() {
+            if is_in_column && columns == column_number {
+                return offset;
+            }
+            is_in_column = false;
+        } else if !is_in_column {
+            is_in_column = true;
+            columns += 1;
+        }
+    }
+    line.len()
 }
 
 pub(crate) fn read_data_files(
@@ -178,9 +651,10 @@ pub(crate) fn read_data_files(
     let parsec_data = read_parsec_data_from_files(metallicity_index, data_dir)?;
 
     if parsec_data.is_valid() {
+        emit(LoadEvent::MetallicityLoaded { metallicity_index });
         Ok(parsec_data)
     } else {
-        let metallicity = METALLICITY_NAMES[metallicity_index];
+        let metallicity = current_track_set().metallicity_names()[metallicity_index];
         let message = format!("Parsec Data for metallicity {metallicity} is empty.");
         Err(ParsecAccessError::DataNotAvailable(message))
     }
@@ -191,24 +665,76 @@ fn read_parsec_data_from_files(
     data_dir: &Path,
 ) -> Result<ParsecData, ParsecAccessError> {
     ensure_data_files(metallicity_index)?;
-    let data_dir_name = METALLICITY_ARCHIVES[metallicity_index].replace(".tar.gz", "");
+    let data_dir_name = archive_dir_name(metallicity_index);
     let folder_path = data_dir.join(PathBuf::from(data_dir_name));
-    let filepaths = FILENAMES[metallicity_index];
+    let config = current_config();
+    let track_set = config.get_track_set();
+    let mass_indices = config.mass_indices(metallicity_index);
+    let filepaths = &track_set.filenames()[metallicity_index][mass_indices.clone()];
+    emit(LoadEvent::ParsingStarted { metallicity_index });
+    if let Some(contents) = track_set.embedded_files() {
+        return ParsecData::from_readers(
+            track_set.metallicities_in_mass_fraction()[metallicity_index],
+            track_set.masses()[metallicity_index][mass_indices.clone()].to_vec(),
+            contents[metallicity_index][mass_indices]
+                .iter()
+                .map(|content| content.as_bytes()),
+        );
+    }
     let mut parsec_data = ParsecData {
-        metallicity_in_mass_fraction: METALLICITIES_IN_MASS_FRACTION[metallicity_index],
+        metallicity_in_mass_fraction: track_set.metallicities_in_mass_fraction()[metallicity_index],
+        masses_in_solar: Cow::Borrowed(&track_set.masses()[metallicity_index][mass_indices]),
         data: Vec::new(),
     };
 
-    let data: Vec<_> = filepaths
+    let read_all = || {
+        read_trajectory_files(
+            metallicity_index,
+            &folder_path,
+            filepaths,
+            config.is_strict(),
+        )
+    };
+    let data = match config.get_threads() {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| ParsecAccessError::Other(e.to_string()))?
+            .install(read_all)?,
+        None => read_all()?,
+    };
+
+    parsec_data.data.extend(data);
+    Ok(parsec_data)
+}
+
+/// Reads the trajectory files in parallel.
+///
+/// The getters rely on the trajectory at a mass index belonging to the mass at that index of the track set,
+/// so the trajectories are returned in the order of the filenames, which is the order of the masses.
+/// Collecting an indexed parallel iterator into a Vec preserves that order, regardless of which file finishes parsing first.
+fn read_trajectory_files(
+    metallicity_index: usize,
+    folder_path: &Path,
+    filepaths: &[&str],
+    strict: bool,
+) -> Result<Vec<Trajectory>, ParsecAccessError> {
+    filepaths
         .par_iter()
         .map(|filepath| {
             let filepath = folder_path.join(filepath);
-            read_trajectory_file(filepath)
+            read_trajectory_file(metallicity_index, filepath, strict)
         })
-        .collect::<Result<_, _>>()?;
+        .collect()
+}
 
-    parsec_data.data.extend(data);
-    Ok(parsec_data)
+fn archive_dir_name(metallicity_index: usize) -> String {
+    archive_dir_name_for(&current_config(), metallicity_index)
+}
+
+fn archive_dir_name_for(config: &ParsecConfig, metallicity_index: usize) -> String {
+    let archive_name = config.get_track_set().archives()[metallicity_index];
+    archive_name.replace(".tar.gz", "")
 }
 
 fn is_header(line: &str) -> bool {
@@ -217,9 +743,16 @@ fn is_header(line: &str) -> bool {
 }
 
 pub(crate) fn get_data_dir() -> Result<PathBuf, ParsecAccessError> {
+    let config = current_config();
+    if let Some(data_dir) = config.get_data_dir() {
+        return Ok(data_dir.clone());
+    }
     let top_level_domain = "".to_string();
-    let author = "the_comamba".to_string();
-    let app_name = current_app_name();
+    let author = config.get_author().to_string();
+    let app_name = match config.get_app_name() {
+        Some(app_name) => app_name.to_string(),
+        None => current_app_name(),
+    };
     let strategy_args = AppStrategyArgs {
         top_level_domain,
         author,
@@ -236,15 +769,225 @@ fn current_app_name() -> String {
 
 #[cfg(test)]
 mod test {
+    use astro_units::mass::solar_mass;
+    use uom::si::time::year;
+
+    use crate::access::metallicity::{METALLICITIES_IN_MASS_FRACTION, METALLICITY_NAMES};
+
     use super::*;
+
+    #[test]
+    fn repeated_and_out_of_order_ages_are_removed() {
+        let ages = [1., 2., 2., 1.5, 3.];
+        let mut lines = vec![];
+        for &age in ages.iter() {
+            push_if_monotonic(&mut lines, ParsecLine::sun_like(age));
+        }
+        let kept: Vec<f64> = lines.iter().map(|line| line.age.get::<year>()).collect();
+        assert_eq!(kept, vec![1., 2., 3.]);
+    }
+
+    #[test]
+    fn missing_trajectory_file_names_metallicity_and_file() {
+        let file_path = PathBuf::from("this_folder_does_not_exist")
+            .join("Z0.0001Y0.249OUTA1.74_F7_M000.100.DAT");
+        let result = read_trajectory_file(0, file_path, false);
+        let Err(ParsecAccessError::DataNotAvailable(message)) = result else {
+            panic!("a missing file should be reported as unavailable data");
+        };
+        assert!(message.contains(METALLICITY_NAMES[0]), "{message}");
+        assert!(
+            message.contains("'Z0.0001Y0.249OUTA1.74_F7_M000.100.DAT'"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn trajectories_are_read_in_order_of_filenames() {
+        let folder_path = std::env::temp_dir().join(format!("{PACKAGE_NAME}_order_test"));
+        let _ = fs::remove_dir_all(&folder_path);
+        fs::create_dir_all(&folder_path).expect("the test folder should be creatable");
+        let masses: Vec<usize> = (1..=32).collect();
+        let filenames: Vec<String> = (0..masses.len()).map(|i| format!("{i}.DAT")).collect();
+        for (filename, mass) in filenames.iter().zip(&masses) {
+            // Heavier stars get longer files, so that they would finish parsing last if the order was not preserved.
+            let content: String = (1..=mass * 20)
+                .map(|age| format!("0 {mass} {age}e6 0.0 3.76 10.84\n"))
+                .collect();
+            fs::write(folder_path.join(filename), content)
+                .expect("the test file should be writable");
+        }
+        let filenames: Vec<&str> = filenames.iter().map(String::as_str).rev().collect();
+        let result = read_trajectory_files(0, &folder_path, &filenames, false);
+        let _ = fs::remove_dir_all(&folder_path);
+        let initial_masses: Vec<usize> = result
+            .expect("the test files should be readable")
+            .iter()
+            .map(|trajectory| trajectory.initial_mass.get::<solar_mass>().round() as usize)
+            .collect();
+        let expected: Vec<usize> = masses.into_iter().rev().collect();
+        assert_eq!(initial_masses, expected);
+    }
+
+    #[test]
+    fn data_is_parsed_from_readers() {
+        let light = "MODELL MASS AGE LOG_L LOG_TE LOG_R\n0 1.0 1e6 0.0 3.76 10.84\n";
+        let heavy = "0 2.0 1e6 1.2 3.95 11.02\n1 2.0 1e8 1.3 3.94 11.05\n";
+        let data =
+            ParsecData::from_readers(0.01, vec![1., 2.], [light.as_bytes(), heavy.as_bytes()])
+                .expect("the data should be parsable");
+        assert_eq!(data.lines_per_mass(), vec![1, 2]);
+        assert_eq!(*data.masses_in_solar, [1., 2.]);
+    }
+
+    #[test]
+    fn parsing_no_readers_fails() {
+        let readers: Vec<&[u8]> = vec![];
+        assert!(ParsecData::from_readers(0.01, vec![], readers).is_err());
+    }
+
+    #[test]
+    fn parsing_readers_with_mismatching_masses_fails() {
+        let track = "MODELL MASS AGE LOG_L LOG_TE LOG_R\n0 1.0 1e6 0.0 3.76 10.84\n";
+        let result = ParsecData::from_readers(0.01, vec![1., 2.], [track.as_bytes()]);
+        assert!(matches!(result, Err(ParsecAccessError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn parsing_readers_with_unsorted_masses_fails() {
+        let track = "MODELL MASS AGE LOG_L LOG_TE LOG_R\n0 1.0 1e6 0.0 3.76 10.84\n";
+        let result =
+            ParsecData::from_readers(0.01, vec![2., 1.], [track.as_bytes(), track.as_bytes()]);
+        assert!(matches!(result, Err(ParsecAccessError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn trimming_keeps_only_required_columns() {
+        let file_path = std::env::temp_dir().join(format!("{PACKAGE_NAME}_trim_test.dat"));
+        fs::write(
+            &file_path,
+            "MODELL MASS AGE\n1 0.8 1e5 extra\n2 0.8 2e5 extra",
+        )
+        .expect("the test file should be writable");
+        let result = trim_file(&file_path, 3);
+        assert!(result.is_ok(), "{:?}", result.err());
+        let trimmed = fs::read_to_string(&file_path).expect("the trimmed file should be readable");
+        let _ = fs::remove_file(&file_path);
+        assert_eq!(trimmed, "MODELL MASS AGE\n1 0.8 1e5\n2 0.8 2e5\n");
+    }
+
+    #[test]
+    fn trimming_preserves_formatting_of_kept_columns() {
+        let line = "  1   0.80000\t1.000e+05  extra columns";
+        assert_eq!(&line[..end_of_column(line, 3)], "  1   0.80000\t1.000e+05");
+        assert_eq!(&line[..end_of_column(line, 10)], line);
+        assert_eq!(end_of_column(line, 0), 0);
+    }
+
+    #[test]
+    fn validating_missing_pre_extracted_data_fails() {
+        let config = ParsecConfig::new().pre_extracted(true);
+        let folder_path = PathBuf::from("this_folder_does_not_exist");
+        let result = validate_data_files(0, &folder_path, &config);
+        assert!(matches!(
+            result,
+            Err(ParsecAccessError::DataNotAvailable(_))
+        ));
+    }
+
+    #[test]
+    fn unpacking_html_page_fails_with_clear_error() {
+        let archive_path = std::env::temp_dir().join(format!("{PACKAGE_NAME}_html_test.tar.gz"));
+        fs::write(
+            &archive_path,
+            "<html><body>Service unavailable</body></html>",
+        )
+        .expect("the test file should be writable");
+        let result = unpack_archive(&archive_path, &std::env::temp_dir(), "test.tar.gz");
+        let _ = fs::remove_file(&archive_path);
+        assert!(matches!(result, Err(ParsecAccessError::InvalidData(_))));
+    }
+
+    #[test]
+    fn local_archive_is_unpacked_and_kept() {
+        let data_dir = std::env::temp_dir().join(format!("{PACKAGE_NAME}_local_archive_test"));
+        let _ = fs::remove_dir_all(&data_dir);
+        create_data_dir(&data_dir).expect("the test dir should be creatable");
+        let archive_path = data_dir.join("test.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&archive_path).expect("the test archive should be writable"),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        let content = b"1 0.8 1e5";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "test/track.DAT", &content[..])
+            .expect("the test archive should be writable");
+        builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .expect("the test archive should be writable");
+
+        let result = unpack_local_archive(0, &archive_path, &data_dir);
+        let unpacked = fs::read_to_string(data_dir.join("test").join("track.DAT"));
+        let is_kept = archive_path.is_file();
+        let _ = fs::remove_dir_all(&data_dir);
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(unpacked.ok().as_deref(), Some("1 0.8 1e5"));
+        assert!(is_kept);
+    }
+
+    #[test]
+    fn archive_size_mismatch_is_reported() {
+        let archive_path = std::env::temp_dir().join(format!("{PACKAGE_NAME}_size_test.part"));
+        fs::write(&archive_path, [0u8; 10]).expect("the test file should be writable");
+        assert!(verify_archive_size(&archive_path, None, "test.tar.gz").is_ok());
+        assert!(verify_archive_size(&archive_path, Some(10), "test.tar.gz").is_ok());
+        let too_short = verify_archive_size(&archive_path, Some(20), "test.tar.gz");
+        let is_kept = archive_path.exists();
+        let too_long = verify_archive_size(&archive_path, Some(5), "test.tar.gz");
+        let is_removed = !archive_path.exists();
+        let _ = fs::remove_file(&archive_path);
+        assert!(matches!(too_short, Err(ParsecAccessError::InvalidData(_))));
+        assert!(matches!(too_long, Err(ParsecAccessError::InvalidData(_))));
+        assert!(is_kept);
+        assert!(is_removed);
+    }
+
+    #[test]
+    fn data_dir_is_created_recursively() {
+        let top_dir = std::env::temp_dir().join(format!("{PACKAGE_NAME}_create_test"));
+        let data_dir = top_dir.join("nested");
+        let _ = fs::remove_dir_all(&top_dir);
+        let result = create_data_dir(&data_dir);
+        let is_dir = data_dir.is_dir();
+        let _ = fs::remove_dir_all(&top_dir);
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert!(is_dir);
+    }
+
+    #[test]
+    fn validating_empty_prepared_data_dir_fails() {
+        let data_dir = PathBuf::from("this_folder_does_not_exist");
+        let config = ParsecConfig::new().data_dir(&data_dir).pre_extracted(true);
+        let result = validate_prepared_data(&data_dir, &config);
+        assert!(matches!(
+            result,
+            Err(ParsecAccessError::DataNotAvailable(_))
+        ));
+    }
+
     #[test]
     #[ignore] // This test manipulates the data files while other tests try to read them
     fn reducing_data() {
         for (metallicity_index, _) in METALLICITIES_IN_MASS_FRACTION.iter().enumerate() {
             let result = ensure_data_files(metallicity_index);
-            assert!(result.is_ok(), "{}", result.unwrap_err());
+            assert!(result.is_ok(), "{:?}", result.err());
             let result = reduce_persisted_data(metallicity_index);
-            assert!(result.is_ok(), "{}", result.unwrap_err());
+            assert!(result.is_ok(), "{:?}", result.err());
         }
     }
 }
@@ -1,18 +1,22 @@
 //! Provides a set of api functions exposing the main functionality of this crate.
+//! Indices can be passed as bare usize values or as the newtypes of the index module.
 
 use astro_units::mass::solar_mass;
+use std::path::Path;
 use uom::si::{
-    f64::{Mass, Time},
+    f64::{Mass, ThermodynamicTemperature, Time},
+    thermodynamic_temperature::kelvin,
     time::year,
 };
 
 use crate::{
-    access::{
-        data::DATA,
-        masses::MASSES,
-        metallicity::{METALLICITIES_IN_DEX, METALLICITIES_IN_MASS_FRACTION},
-    },
+    bolometric_correction::bracket,
+    config::{current_mass_indices, current_solar_metallicity, current_track_set},
+    constants::SOLAR_METALLICITY_IN_MASS_FRACTION,
     data::ParsecData,
+    error::ParsecAccessError,
+    file::{archive_size, ensure_data_files, export_data_files, is_download_pending},
+    index::{AgeIndex, MassIndex, MetallicityIndex},
     line::ParsecLine,
     trajectory::Trajectory,
 };
@@ -27,14 +31,143 @@ use crate::{
 /// assert!(is_data_ready());
 /// ```
 pub fn is_data_ready() -> bool {
-    for i in 0..METALLICITIES_IN_MASS_FRACTION.len() {
-        if !DATA[i].is_valid() {
+    for i in 0..num_metallicities() {
+        if !ParsecData::loaded(i).is_valid() {
             return false;
         }
     }
     true
 }
 
+/// Returns whether valid data for the metallicity has already been read into memory, without reading it.
+///
+/// This is distinct from is_data_ready(), which loads the data, and from pending_downloads(), which only inspects the file system.
+/// In a forked worker model, workers can use it to assert that they inherited the data from their parent, instead of loading it again.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{is_data_ready, is_loaded};
+///
+/// assert!(is_data_ready());
+/// assert!(is_loaded(0));
+/// ```
+pub fn is_loaded(metallicity_index: impl Into<MetallicityIndex>) -> bool {
+    let metallicity_index = metallicity_index.into().0;
+    ParsecData::is_loaded(metallicity_index)
+}
+
+/// Returns the indices of the metallicities whose data is not cached on disk yet, and would therefore be downloaded on first access.
+/// This only inspects the file system, so it can be used to inform the user before the data is loaded.
+///
+/// In offline mode and for pre-extracted data directories nothing is downloaded, so the list is always empty.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{is_data_ready, pending_downloads};
+///
+/// let pending = pending_downloads();
+/// if !pending.is_empty() {
+///     println!("{} metallicities need to be downloaded first.", pending.len());
+/// }
+/// assert!(is_data_ready());
+/// assert!(pending_downloads().is_empty());
+/// ```
+pub fn pending_downloads() -> Vec<usize> {
+    (0..num_metallicities())
+        .filter(|&metallicity_index| is_download_pending(metallicity_index))
+        .collect()
+}
+
+/// Reports what accessing the data would download, without downloading anything.
+/// Returns the index of each metallicity that is not cached yet, together with the size of its archive in bytes.
+///
+/// The sizes are obtained by asking the server, so this requires a network connection unless nothing is pending.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::download_plan;
+///
+/// let plan = download_plan().unwrap();
+/// let total_bytes: u64 = plan.iter().map(|(_, bytes)| bytes).sum();
+/// println!("{} archives with {} bytes in total need to be downloaded.", plan.len(), total_bytes);
+/// ```
+pub fn download_plan() -> Result<Vec<(usize, u64)>, ParsecAccessError> {
+    pending_downloads()
+        .into_iter()
+        .map(|metallicity_index| Ok((metallicity_index, archive_size(metallicity_index)?)))
+        .collect()
+}
+
+/// Makes sure that the data files of the metallicity closest to the given mass fraction are cached on disk, downloading them if necessary.
+/// Returns the index of that metallicity.
+///
+/// Only this single metallicity is fetched, and its data is not read into memory yet.
+/// This allows prefetching exactly the grid point a user asked for.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{ensure_metallicity_ready_by_value, pending_downloads};
+///
+/// let metallicity_index = ensure_metallicity_ready_by_value(0.014).unwrap();
+/// assert!(!pending_downloads().contains(&metallicity_index));
+/// ```
+pub fn ensure_metallicity_ready_by_value(mass_fraction: f64) -> Result<usize, ParsecAccessError> {
+    let metallicity_index = get_closest_metallicity_index_from_mass_fraction(mass_fraction);
+    ensure_data_files(metallicity_index)?;
+    Ok(metallicity_index)
+}
+
+/// Reads the data for a given metallicity from disk if it is not loaded yet, returning an error instead of invalid data if that fails.
+///
+/// The getters hand out references into the loaded data that live for the rest of the program, so loaded data is never replaced.
+/// Calling this for a metallicity that is already loaded therefore returns an error, and a restart is needed to pick up updated data files.
+/// Metallicities whose data could not be read are not considered loaded, so this can be used to retry once the files are fixed.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_data, is_data_ready, reload_metallicity};
+///
+/// assert!(is_data_ready());
+/// assert!(reload_metallicity(0).is_err());
+/// assert!(get_data(0).metallicity_in_mass_fraction > 0.);
+/// ```
+pub fn reload_metallicity(
+    metallicity_index: impl Into<MetallicityIndex>,
+) -> Result<(), ParsecAccessError> {
+    let metallicity_index = metallicity_index.into().0;
+    if ParsecData::is_loaded(metallicity_index) {
+        return Err(ParsecAccessError::InvalidInput(format!(
+            "The data for metallicity index {metallicity_index} is already loaded and cannot be replaced"
+        )));
+    }
+    ParsecData::load(metallicity_index).map(|_| ())
+}
+
+/// Copies the reduced data files of every metallicity to the destination directory, for example to bundle the exact inputs of an analysis in a reproducibility archive.
+///
+/// The files are laid out like in the data directory, so the destination can later be passed to use_prepared_data().
+/// If a mass range is configured, only the files of the loaded masses are copied.
+/// Data that is not cached yet is downloaded first.
+///
+/// # Example
+/// ```no_run
+/// use parsec_access::config::{init, ParsecConfig};
+/// use parsec_access::getters::{export_reduced_data, num_metallicities};
+/// use astro_units::mass::solar_mass;
+/// use uom::si::f64::Mass;
+///
+/// let min = Mass::new::<solar_mass>(0.9);
+/// let max = Mass::new::<solar_mass>(1.1);
+/// init(ParsecConfig::new().mass_range(min, max)).unwrap();
+/// let dest = std::env::temp_dir().join("parsec_access_export_example");
+/// assert!(export_reduced_data(&dest).is_ok());
+/// assert_eq!(std::fs::read_dir(&dest).unwrap().count(), num_metallicities());
+/// std::fs::remove_dir_all(&dest).unwrap();
+/// ```
+pub fn export_reduced_data(dest: &Path) -> Result<(), ParsecAccessError> {
+    export_data_files(dest)
+}
+
 /// Fetches a reference to the ParsecData object for a given metallicity.
 /// This is functionally similar to get_closest_data, but faster by about a factor of 10.
 /// To find the correct metallicity index, use get_closest_metallicity_index_from_mass_fraction.
@@ -53,8 +186,9 @@ pub fn is_data_ready() -> bool {
 /// assert!(data.metallicity_in_mass_fraction > 0.);
 /// let first_trajectory = &data[0];
 /// ```
-pub fn get_data(metallicity_index: usize) -> &'static ParsecData {
-    DATA[metallicity_index]
+pub fn get_data(metallicity_index: impl Into<MetallicityIndex>) -> &'static ParsecData {
+    let metallicity_index = metallicity_index.into().0;
+    ParsecData::loaded(metallicity_index)
 }
 
 /// Fetches a reference to the ParsecData object for the metallicity that is closest to the provided value.
@@ -80,6 +214,26 @@ pub fn get_closest_data(mass_fraction: f64) -> &'static ParsecData {
     get_data(metallicity_index)
 }
 
+/// Fetches a reference to the ParsecData object for the metallicity that is closest to the provided [Fe/H] in dex.
+/// This is the counterpart of get_closest_data() for observational metallicities, see get_metallicities_in_fe_dex() for the unit conversion.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_data_by_fe_dex, is_data_ready};
+///
+/// assert!(is_data_ready());
+/// let data = get_closest_data_by_fe_dex(0.);
+/// assert!(data.metallicity_in_fe_dex().abs() < 0.2);
+/// ```
+pub fn get_closest_data_by_fe_dex(fe_dex: f64) -> &'static ParsecData {
+    get_data(get_closest_metallicity_index_from_fe_dex(fe_dex))
+}
+
 /// Fetches a reference to the trajectory for a given metallicity and mass.
 /// This is functionally similar to get_closest_trajectory, but faster by about a factor of 10.
 /// To find the correct metallicity and mass index, use get_closest_metallicity_index_from_mass_fraction and get_closest_mass_index.
@@ -100,8 +254,89 @@ pub fn get_closest_data(mass_fraction: f64) -> &'static ParsecData {
 /// assert!(trajectory.initial_mass.get::<solar_mass>() > 0.);
 /// assert!(trajectory.lifetime.get::<year>() > 0.);
 /// ```
-pub fn get_trajectory(metallicity_index: usize, mass_index: usize) -> &'static Trajectory {
-    &DATA[metallicity_index].data[mass_index]
+pub fn get_trajectory(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+) -> &'static Trajectory {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    &get_data(metallicity_index).data[mass_index]
+}
+
+/// Returns the lifetime of the star with the given metallicity and mass, which is the age of the last entry of its trajectory.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_lifetime, is_data_ready};
+/// use uom::si::time::year;
+///
+/// assert!(is_data_ready());
+/// assert!(get_lifetime(1, 2).get::<year>() > 0.);
+/// ```
+pub fn get_lifetime(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+) -> Time {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    get_trajectory(metallicity_index, mass_index).lifetime
+}
+
+/// Returns the initial mass of the star with the given metallicity and mass index.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_initial_mass, get_masses_in_solar, is_data_ready};
+/// use astro_units::mass::solar_mass;
+///
+/// assert!(is_data_ready());
+/// let initial_mass = get_initial_mass(1, 2).get::<solar_mass>();
+/// assert!((initial_mass - get_masses_in_solar(1)[2]).abs() < 1e-2 * initial_mass);
+/// ```
+pub fn get_initial_mass(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+) -> Mass {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    get_trajectory(metallicity_index, mass_index).initial_mass
+}
+
+/// Returns whether a star of the given metallicity and mass is still alive at the given age, see Trajectory::is_alive_at().
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{is_alive, is_data_ready};
+/// use astro_units::time::gigayear;
+/// use uom::si::f64::Time;
+///
+/// assert!(is_data_ready());
+/// assert!(is_alive(1, 2, Time::new::<gigayear>(0.)));
+/// assert!(!is_alive(1, 2, Time::new::<gigayear>(1e6)));
+/// ```
+pub fn is_alive(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+    age: Time,
+) -> bool {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    get_trajectory(metallicity_index, mass_index).is_alive_at(age)
 }
 
 /// Fetches a reference to the trajectory for the metallicity and mass that are closest to the provided values.
@@ -130,6 +365,64 @@ pub fn get_closest_trajectory(mass_fraction: f64, mass: Mass) -> &'static Trajec
     get_trajectory(metallicity_index, mass_index)
 }
 
+/// Fetches a reference to the trajectory for the metallicity closest to the provided [Fe/H] in dex and the closest mass.
+/// This is the counterpart of get_closest_trajectory() for observational metallicities, see get_metallicities_in_fe_dex() for the unit conversion.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_trajectory_by_fe_dex, is_data_ready};
+/// use astro_units::mass::solar_mass;
+/// use uom::si::f64::Mass;
+///
+/// assert!(is_data_ready());
+/// let trajectory = get_closest_trajectory_by_fe_dex(0., Mass::new::<solar_mass>(1.));
+/// assert!(trajectory.initial_mass.get::<solar_mass>() > 0.9);
+/// assert!(trajectory.initial_mass.get::<solar_mass>() < 1.1);
+/// ```
+pub fn get_closest_trajectory_by_fe_dex(fe_dex: f64, mass: Mass) -> &'static Trajectory {
+    let metallicity_index = get_closest_metallicity_index_from_fe_dex(fe_dex);
+    let mass_index = get_closest_mass_index(metallicity_index, mass);
+    get_trajectory(metallicity_index, mass_index)
+}
+
+/// Fetches a reference to the trajectory for the metallicity and mass that are closest to the provided values, like get_closest_trajectory().
+/// Additionally returns the chosen metallicity and mass index, which can be passed on to the faster index based getters in subsequent calls.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_trajectory_indexed, get_parameters, is_data_ready};
+/// use astro_units::mass::solar_mass;
+/// use uom::si::f64::Mass;
+///
+/// assert!(is_data_ready());
+/// let (metallicity_index, mass_index, trajectory) = get_closest_trajectory_indexed(0.01, Mass::new::<solar_mass>(1.));
+/// assert!(trajectory.initial_mass.get::<solar_mass>() > 0.9);
+/// let parameters = get_parameters(metallicity_index, mass_index, 0);
+/// assert!(parameters.age == trajectory[0].age);
+/// ```
+pub fn get_closest_trajectory_indexed(
+    mass_fraction: f64,
+    mass: Mass,
+) -> (usize, usize, &'static Trajectory) {
+    let metallicity_index = get_closest_metallicity_index_from_mass_fraction(mass_fraction);
+    let mass_index = get_closest_mass_index(metallicity_index, mass);
+    (
+        metallicity_index,
+        mass_index,
+        get_trajectory(metallicity_index, mass_index),
+    )
+}
+
 /// Fetches a reference to the ParsecLine object for a given metallicity, mass, and age.
 /// This is functionally similar to get_closest_parameters, but faster by about a factor of 10.
 /// To find the correct metallicity, mass, and age index, use get_closest_metallicity_index_from_mass_fraction, get_closest_mass_index, and get_closest_age_index.
@@ -154,11 +447,43 @@ pub fn get_closest_trajectory(mass_fraction: f64, mass: Mass) -> &'static Trajec
 /// assert!(parameters.radius.get::<kilometer>() > 0.);
 /// ```
 pub fn get_parameters(
-    metallicity_index: usize,
-    mass_index: usize,
-    age_index: usize,
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+    age_index: impl Into<AgeIndex>,
+) -> &'static ParsecLine {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    let age_index = age_index.into().0;
+    &get_data(metallicity_index).data[mass_index][age_index]
+}
+
+/// Fetches a reference to the ParsecLine object on the zero-age main sequence for a given metallicity and mass.
+/// This is the first entry of the corresponding trajectory.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_parameters, get_zams, is_data_ready};
+/// use uom::si::{length::kilometer, thermodynamic_temperature::kelvin};
+///
+/// assert!(is_data_ready());
+/// let zams = get_zams(1, 2);
+/// assert!(zams.age == get_parameters(1, 2, 0).age);
+/// println!("Luminosity at ZAMS: {} sol", zams.luminosity_in_solar);
+/// println!("Temperature at ZAMS: {} K", zams.temperature.get::<kelvin>());
+/// println!("Radius at ZAMS: {} km", zams.radius.get::<kilometer>());
+/// ```
+pub fn get_zams(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
 ) -> &'static ParsecLine {
-    &DATA[metallicity_index].data[mass_index][age_index]
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    get_trajectory(metallicity_index, mass_index).zams()
 }
 
 /// Fetches a reference to the ParsecLine object for the metallicity, mass, and age that are closest to the provided values.
@@ -185,11 +510,150 @@ pub fn get_parameters(
 /// ```
 pub fn get_closest_parameters(mass_fraction: f64, mass: Mass, age: Time) -> &'static ParsecLine {
     let metallicity_index = get_closest_metallicity_index_from_mass_fraction(mass_fraction);
+    get_closest_parameters_with_metallicity_index(metallicity_index, mass, age)
+}
+
+/// Fetches a reference to the ParsecLine object for the mass and age that are closest to the provided values, within an already resolved metallicity.
+/// This saves the search for the metallicity index that get_closest_parameters() performs, for example in loops over many stars of the same metallicity.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_metallicity_index_from_mass_fraction, get_closest_parameters, get_closest_parameters_with_metallicity_index, is_data_ready};
+/// use astro_units::{mass::solar_mass, time::gigayear};
+/// use uom::si::f64::{Mass, Time};
+///
+/// assert!(is_data_ready());
+/// let metallicity_index = get_closest_metallicity_index_from_mass_fraction(0.01);
+/// for mass_in_solar in [0.8, 1., 1.2] {
+///     let mass = Mass::new::<solar_mass>(mass_in_solar);
+///     let age = Time::new::<gigayear>(1.);
+///     let parameters = get_closest_parameters_with_metallicity_index(metallicity_index, mass, age);
+///     assert_eq!(parameters, get_closest_parameters(0.01, mass, age));
+/// }
+/// ```
+pub fn get_closest_parameters_with_metallicity_index(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass: Mass,
+    age: Time,
+) -> &'static ParsecLine {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = get_closest_mass_index(metallicity_index, mass);
+    let age_index = get_closest_age_index(metallicity_index, mass_index, age);
+    get_parameters(metallicity_index, mass_index, age_index)
+}
+
+/// Fetches a reference to the ParsecLine object for the metallicity closest to the provided [Fe/H] in dex and the closest mass and age.
+/// This is the counterpart of get_closest_parameters() for observational metallicities, see get_metallicities_in_fe_dex() for the unit conversion.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_parameters_by_fe_dex, is_data_ready};
+/// use astro_units::{mass::solar_mass, time::gigayear};
+/// use uom::si::f64::{Mass, Time};
+///
+/// assert!(is_data_ready());
+/// let parameters = get_closest_parameters_by_fe_dex(0., Mass::new::<solar_mass>(1.), Time::new::<gigayear>(1.));
+/// assert!(parameters.mass > Mass::new::<solar_mass>(0.9));
+/// assert!(parameters.mass < Mass::new::<solar_mass>(1.1));
+/// ```
+pub fn get_closest_parameters_by_fe_dex(fe_dex: f64, mass: Mass, age: Time) -> &'static ParsecLine {
+    let metallicity_index = get_closest_metallicity_index_from_fe_dex(fe_dex);
     let mass_index = get_closest_mass_index(metallicity_index, mass);
     let age_index = get_closest_age_index(metallicity_index, mass_index, age);
     get_parameters(metallicity_index, mass_index, age_index)
 }
 
+/// Collects the parameters of a star with the given initial mass and age for every available metallicity.
+/// Each entry pairs the metallicity in units of the mass fraction Z with the corresponding parameters.
+///
+/// For each metallicity, the trajectory with the closest initial mass is chosen, and its parameters are interpolated at the requested age.
+/// Ages beyond the lifetime of a trajectory yield its last entry.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_parameters_over_metallicity, is_data_ready, num_metallicities};
+/// use astro_units::{mass::solar_mass, time::gigayear};
+/// use uom::si::{f64::{Mass, Time}, thermodynamic_temperature::kelvin};
+///
+/// assert!(is_data_ready());
+/// let parameters = get_parameters_over_metallicity(Mass::new::<solar_mass>(1.), Time::new::<gigayear>(1.));
+/// assert_eq!(parameters.len(), num_metallicities());
+/// for (mass_fraction, line) in parameters {
+///     println!("Z = {}: T_eff = {} K", mass_fraction, line.temperature.get::<kelvin>());
+/// }
+/// ```
+pub fn get_parameters_over_metallicity(mass: Mass, age: Time) -> Vec<(f64, ParsecLine)> {
+    metallicities()
+        .map(|(metallicity_index, mass_fraction)| {
+            let mass_index = get_closest_mass_index(metallicity_index, mass);
+            let trajectory = get_trajectory(metallicity_index, mass_index);
+            (
+                mass_fraction,
+                trajectory.interpolate_at_year(age.get::<year>()),
+            )
+        })
+        .collect()
+}
+
+/// Collects the parameters of a star with the given initial mass and age for every available metallicity, like get_parameters_over_metallicity().
+/// Instead of silently clamping, an error is returned if the mass lies outside of the mass grid of any metallicity,
+/// or if the star is not alive at the given age for any metallicity.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_parameters_over_metallicity_checked, is_data_ready};
+/// use astro_units::{mass::solar_mass, time::gigayear};
+/// use uom::si::f64::{Mass, Time};
+///
+/// assert!(is_data_ready());
+/// let mass = Mass::new::<solar_mass>(1.);
+/// assert!(get_parameters_over_metallicity_checked(mass, Time::new::<gigayear>(1.)).is_ok());
+/// assert!(get_parameters_over_metallicity_checked(mass, Time::new::<gigayear>(100.)).is_err());
+/// ```
+pub fn get_parameters_over_metallicity_checked(
+    mass: Mass,
+    age: Time,
+) -> Result<Vec<(f64, ParsecLine)>, ParsecAccessError> {
+    metallicities()
+        .map(|(metallicity_index, mass_fraction)| {
+            let (mass_index, clamp) = get_closest_index_with_clamp(
+                get_masses_in_solar(metallicity_index),
+                mass.get::<solar_mass>(),
+            );
+            if clamp != Clamp::Within {
+                let message = format!(
+                    "Mass {} solar masses lies outside of the mass grid of metallicity {}",
+                    mass.get::<solar_mass>(),
+                    mass_fraction
+                );
+                return Err(ParsecAccessError::OutOfRange(message));
+            }
+            let line = get_trajectory(metallicity_index, mass_index).interpolate_checked(age)?;
+            Ok((mass_fraction, line))
+        })
+        .collect()
+}
+
 /// Returns a reference to the array of available metallicities in units of the mass fractions Z.
 ///
 /// # Example
@@ -203,7 +667,7 @@ pub fn get_closest_parameters(mass_fraction: f64, mass: Mass, age: Time) -> &'st
 /// }
 /// ```
 pub fn get_metallicities_in_mass_fractions() -> &'static [f64] {
-    &METALLICITIES_IN_MASS_FRACTION
+    current_track_set().metallicities_in_mass_fraction()
 }
 
 /// Returns a reference to the array of available metallicities in units of dex for the element iron,
@@ -226,7 +690,9 @@ pub fn get_metallicities_in_mass_fractions() -> &'static [f64] {
 /// [Fe/H] = log10(a * m_M / m_tot) - log10(a * m_M / m_tot)_sun
 ///        = log10(Z / Z_sun) .
 ///
-/// The solar metallicity is Z_sun = 0.0122.
+/// The values returned here are always relative to Z_sun = 0.0122, see constants::SOLAR_METALLICITY_IN_MASS_FRACTION, regardless of the configuration.
+/// The getters taking [Fe/H] as input and ParsecData::metallicity_in_fe_dex() use the solar metallicity set with config::ParsecConfig::solar_metallicity() instead.
+/// Both only agree for the default solar metallicity, so compare such values with get_metallicities_relative_to_solar().
 ///
 /// # Example
 /// ```
@@ -239,7 +705,64 @@ pub fn get_metallicities_in_mass_fractions() -> &'static [f64] {
 /// }
 /// ```
 pub fn get_metallicities_in_fe_dex() -> &'static [f64] {
-    &METALLICITIES_IN_DEX
+    current_track_set().metallicities_in_dex()
+}
+
+/// Returns the available metallicities in units of dex, relative to the solar metallicity set with config::ParsecConfig::solar_metallicity().
+/// This allocates a new vector, so prefer get_metallicities_in_fe_dex() in performance critical code.
+///
+/// # Example
+/// ```
+/// use parsec_access::config::{init, ParsecConfig};
+/// use parsec_access::getters::{get_metallicities_in_fe_dex, get_metallicities_relative_to_solar};
+///
+/// init(ParsecConfig::new().solar_metallicity(0.0244)).unwrap();
+/// let relative = get_metallicities_relative_to_solar();
+/// assert!((relative[0] - get_metallicities_in_fe_dex()[0] + 2f64.log10()).abs() < 1e-8);
+/// ```
+pub fn get_metallicities_relative_to_solar() -> Vec<f64> {
+    let offset = solar_offset_in_dex();
+    current_track_set()
+        .metallicities_in_dex()
+        .iter()
+        .map(|dex| dex + offset)
+        .collect()
+}
+
+/// The shift of the dex values caused by the configured solar metallicity, relative to constants::SOLAR_METALLICITY_IN_MASS_FRACTION.
+fn solar_offset_in_dex() -> f64 {
+    (SOLAR_METALLICITY_IN_MASS_FRACTION / current_solar_metallicity()).log10()
+}
+
+/// Returns the number of available metallicities.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_metallicities_in_mass_fractions, num_metallicities};
+///
+/// assert_eq!(num_metallicities(), get_metallicities_in_mass_fractions().len());
+/// ```
+pub fn num_metallicities() -> usize {
+    current_track_set().metallicities_in_mass_fraction().len()
+}
+
+/// Iterates over the available metallicities, yielding pairs of the metallicity index and the metallicity in units of the mass fraction Z.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_data, is_data_ready, metallicities};
+///
+/// assert!(is_data_ready());
+/// for (metallicity_index, mass_fraction) in metallicities() {
+///     assert_eq!(get_data(metallicity_index).metallicity_in_mass_fraction, mass_fraction);
+/// }
+/// ```
+pub fn metallicities() -> impl Iterator<Item = (usize, f64)> {
+    current_track_set()
+        .metallicities_in_mass_fraction()
+        .iter()
+        .copied()
+        .enumerate()
 }
 
 /// Finds the closest metallicity enum variant to the given mass fraction Z.
@@ -262,7 +785,240 @@ pub fn get_metallicities_in_fe_dex() -> &'static [f64] {
 /// assert_eq!(index, expected);
 /// ```
 pub fn get_closest_metallicity_index_from_mass_fraction(mass_fraction: f64) -> usize {
-    get_closest_index(&METALLICITIES_IN_MASS_FRACTION, mass_fraction)
+    get_closest_index(
+        current_track_set().metallicities_in_mass_fraction(),
+        mass_fraction,
+    )
+}
+
+/// Finds the closest metallicity enum variant to the given mass fraction Z, like get_closest_metallicity_index_from_mass_fraction().
+/// Mass fractions outside of the physical range from 0 to 1 are rejected with an error instead of being clamped.
+///
+/// Such values usually mean that [Fe/H] in dex was passed instead of a mass fraction, which would otherwise silently yield the lowest metallicity.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::try_closest_metallicity_index_from_mass_fraction;
+///
+/// assert!(try_closest_metallicity_index_from_mass_fraction(0.01).is_ok());
+/// assert!(try_closest_metallicity_index_from_mass_fraction(-0.3).is_err());
+/// ```
+pub fn try_closest_metallicity_index_from_mass_fraction(
+    mass_fraction: f64,
+) -> Result<usize, ParsecAccessError> {
+    if !(0. ..=1.).contains(&mass_fraction) {
+        let message = format!(
+            "Metallicity mass fraction {mass_fraction} lies outside of the physical range from 0 to 1. Was [Fe/H] in dex passed instead?"
+        );
+        return Err(ParsecAccessError::InvalidInput(message));
+    }
+    Ok(get_closest_metallicity_index_from_mass_fraction(
+        mass_fraction,
+    ))
+}
+
+/// Finds the closest metallicity enum variant to the given mass fraction Z, like get_closest_metallicity_index_from_mass_fraction().
+/// Additionally returns the absolute difference between the provided mass fraction and the one of the chosen metallicity.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::get_closest_metallicity_index_from_mass_fraction_with_distance;
+///
+/// let (_, distance) = get_closest_metallicity_index_from_mass_fraction_with_distance(0.0101);
+/// assert!((distance - 0.0001).abs() < 1e-8);
+/// ```
+pub fn get_closest_metallicity_index_from_mass_fraction_with_distance(
+    mass_fraction: f64,
+) -> (usize, f64) {
+    get_closest_index_with_distance(
+        current_track_set().metallicities_in_mass_fraction(),
+        mass_fraction,
+    )
+}
+
+/// Describes where a queried value lies relative to the grid it was looked up in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Clamp {
+    /// The value is smaller than the smallest grid value, so the first index was returned.
+    Below,
+    /// The value lies within the range of the grid.
+    Within,
+    /// The value is larger than the largest grid value, so the last index was returned.
+    Above,
+}
+
+/// Finds the closest metallicity enum variant to the given mass fraction, like get_closest_metallicity_index_from_mass_fraction().
+/// Additionally reports whether the mass fraction lies outside of the grid, in which case the returned index is clamped to its end.
+///
+/// Values far outside of the grid often indicate a units mistake, for example passing [Fe/H] in dex instead of a mass fraction.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_metallicity_index_from_mass_fraction_checked, Clamp};
+///
+/// let (_, clamp) = get_closest_metallicity_index_from_mass_fraction_checked(0.01);
+/// assert_eq!(clamp, Clamp::Within);
+/// let (index, clamp) = get_closest_metallicity_index_from_mass_fraction_checked(-0.5);
+/// assert_eq!((index, clamp), (0, Clamp::Below));
+/// let (_, clamp) = get_closest_metallicity_index_from_mass_fraction_checked(0.5);
+/// assert_eq!(clamp, Clamp::Above);
+/// ```
+pub fn get_closest_metallicity_index_from_mass_fraction_checked(
+    mass_fraction: f64,
+) -> (usize, Clamp) {
+    get_closest_index_with_clamp(
+        current_track_set().metallicities_in_mass_fraction(),
+        mass_fraction,
+    )
+}
+
+/// Finds the two metallicities that enclose the given mass fraction.
+/// Returns the lower metallicity index, the upper metallicity index, and the fraction of the way from the lower to the upper metallicity, between 0 and 1.
+///
+/// The fraction is measured in log10(Z), which is equivalent to measuring it in [Fe/H].
+/// Mass fractions outside of the grid are clamped, in which case both indices are equal and the fraction is 0.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_metallicity_bracket, get_metallicities_in_mass_fractions};
+///
+/// let metallicities = get_metallicities_in_mass_fractions();
+/// let mass_fraction = (metallicities[3] * metallicities[4]).sqrt();
+/// let (lower, upper, fraction) = get_metallicity_bracket(mass_fraction);
+/// assert_eq!((lower, upper), (3, 4));
+/// assert!((fraction - 0.5).abs() < 1e-8);
+/// ```
+pub fn get_metallicity_bracket(mass_fraction: f64) -> (usize, usize, f64) {
+    let log_metallicities: Vec<f64> = current_track_set()
+        .metallicities_in_mass_fraction()
+        .iter()
+        .map(|z| z.log10())
+        .collect();
+    bracket(&log_metallicities, mass_fraction.log10())
+}
+
+/// Finds the two metallicities that enclose the given [Fe/H] in dex, like get_metallicity_bracket() does for mass fractions.
+/// Returns the lower metallicity index, the upper metallicity index, and the fraction of the way from the lower to the upper metallicity, between 0 and 1.
+///
+/// The fraction is measured linearly in [Fe/H], so dex-native workflows get their interpolation weights without a detour through mass fractions.
+/// Values outside of the grid are clamped, in which case both indices are equal and the fraction is 0.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_bracketing_metallicity_indices_from_fe_dex, get_metallicities_relative_to_solar};
+///
+/// let metallicities = get_metallicities_relative_to_solar();
+/// let fe_dex = 0.25 * metallicities[3] + 0.75 * metallicities[4];
+/// let (lower, upper, fraction) = get_bracketing_metallicity_indices_from_fe_dex(fe_dex);
+/// assert_eq!((lower, upper), (3, 4));
+/// assert!((fraction - 0.75).abs() < 1e-8);
+/// let (lower, upper, fraction) = get_bracketing_metallicity_indices_from_fe_dex(10.);
+/// assert_eq!((lower, upper, fraction), (metallicities.len() - 1, metallicities.len() - 1, 0.));
+/// ```
+pub fn get_bracketing_metallicity_indices_from_fe_dex(fe_dex: f64) -> (usize, usize, f64) {
+    bracket(
+        current_track_set().metallicities_in_dex(),
+        fe_dex - solar_offset_in_dex(),
+    )
+}
+
+/// Creates a synthetic trajectory at a metallicity between the grid points, by interpolating the trajectories with the same mass index of the two enclosing metallicities.
+///
+/// The two trajectories generally differ in lifetime and age sampling. They are therefore resampled at common fractions of their respective lifetimes,
+/// so that corresponding evolutionary phases are blended with each other. The sampling is the union of the sampling of both trajectories.
+/// The blending weight is taken from get_metallicity_bracket(), and mass fractions outside of the grid yield the trajectory of the closest metallicity.
+///
+/// Blending by mass index is only meaningful if the two metallicities share the same mass grid, which can be checked with metallicity_grids_aligned().
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_mass_index, get_interpolated_trajectory, get_metallicity_bracket, get_trajectory, is_data_ready};
+/// use astro_units::mass::solar_mass;
+/// use uom::si::f64::Mass;
+///
+/// assert!(is_data_ready());
+/// let (lower, upper, _) = get_metallicity_bracket(0.012);
+/// let mass_index = get_closest_mass_index(lower, Mass::new::<solar_mass>(1.));
+/// let trajectory = get_interpolated_trajectory(0.012, mass_index);
+/// let lower_lifetime = get_trajectory(lower, mass_index).lifetime;
+/// let upper_lifetime = get_trajectory(upper, mass_index).lifetime;
+/// assert!(trajectory.lifetime >= lower_lifetime.min(upper_lifetime));
+/// assert!(trajectory.lifetime <= lower_lifetime.max(upper_lifetime));
+/// ```
+pub fn get_interpolated_trajectory(
+    mass_fraction: f64,
+    mass_index: impl Into<MassIndex>,
+) -> Trajectory {
+    let mass_index = mass_index.into().0;
+    let (lower_index, upper_index, weight) = get_metallicity_bracket(mass_fraction);
+    let lower = get_trajectory(lower_index, mass_index);
+    if lower_index == upper_index {
+        return lower.clone();
+    }
+    let upper = get_trajectory(upper_index, mass_index);
+
+    let lower_lifetime = lower.lifetime.get::<year>();
+    let upper_lifetime = upper.lifetime.get::<year>();
+    let mut fractions: Vec<f64> = lower
+        .ages_in_years
+        .iter()
+        .map(|age| age / lower_lifetime)
+        .chain(upper.ages_in_years.iter().map(|age| age / upper_lifetime))
+        .collect();
+    fractions.sort_by(f64::total_cmp);
+    fractions.dedup();
+
+    let lines = fractions
+        .iter()
+        .map(|fraction| {
+            ParsecLine::interpolate(
+                &lower.interpolate_at_year(fraction * lower_lifetime),
+                &upper.interpolate_at_year(fraction * upper_lifetime),
+                weight,
+            )
+        })
+        .collect();
+    Trajectory::new(lines)
+}
+
+/// Creates a synthetic trajectory between the grid points like get_interpolated_trajectory().
+/// Instead of silently falling back to the closest metallicity, an error is returned if the mass fraction lies outside of the metallicity grid.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks on the mass index.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_interpolated_trajectory_checked, is_data_ready};
+///
+/// assert!(is_data_ready());
+/// assert!(get_interpolated_trajectory_checked(0.012, 2).is_ok());
+/// assert!(get_interpolated_trajectory_checked(0.5, 2).is_err());
+/// ```
+pub fn get_interpolated_trajectory_checked(
+    mass_fraction: f64,
+    mass_index: impl Into<MassIndex>,
+) -> Result<Trajectory, ParsecAccessError> {
+    let mass_index = mass_index.into().0;
+    let metallicities = current_track_set().metallicities_in_mass_fraction();
+    let (_, clamp) = get_closest_index_with_clamp(metallicities, mass_fraction);
+    if clamp != Clamp::Within {
+        let message = format!(
+            "Mass fraction {} lies outside of the metallicity grid, which covers {} to {}",
+            mass_fraction,
+            metallicities[0],
+            metallicities[metallicities.len() - 1]
+        );
+        return Err(ParsecAccessError::OutOfRange(message));
+    }
+    Ok(get_interpolated_trajectory(mass_fraction, mass_index))
 }
 
 /// Finds the closest metallicity enum variant to the given dex for the element iron.
@@ -274,22 +1030,52 @@ pub fn get_closest_metallicity_index_from_mass_fraction(mass_fraction: f64) -> u
 ///
 /// # Example
 /// ```
-/// use parsec_access::getters::{get_closest_metallicity_index_from_fe_dex, get_metallicities_in_fe_dex};
+/// use parsec_access::getters::{get_closest_metallicity_index_from_fe_dex, get_metallicities_relative_to_solar};
 ///
 /// let index = get_closest_metallicity_index_from_fe_dex(0.);
-/// println!("{} dex is the closest metallicity to 0 dex", get_metallicities_in_fe_dex()[index]);
+/// println!("{} dex is the closest metallicity to 0 dex", get_metallicities_relative_to_solar()[index]);
 /// let index = get_closest_metallicity_index_from_fe_dex(-10.);
 /// let expected = 0;
 /// assert_eq!(index, expected);
 /// let index = get_closest_metallicity_index_from_fe_dex(10.);
-/// let expected = get_metallicities_in_fe_dex().len() - 1;
+/// let expected = get_metallicities_relative_to_solar().len() - 1;
 /// assert_eq!(index, expected);
 /// ```
 pub fn get_closest_metallicity_index_from_fe_dex(fe_dex: f64) -> usize {
-    get_closest_index(&METALLICITIES_IN_DEX, fe_dex)
+    get_closest_index(
+        current_track_set().metallicities_in_dex(),
+        fe_dex - solar_offset_in_dex(),
+    )
+}
+
+/// Returns the index of the metallicity with the given label, or None if no metallicity carries that label.
+///
+/// The label is matched exactly against the PARSEC grid point names like "Z0.0140" (or equivalently "Z0_0140"),
+/// as well as against the names of the downloaded archives, with or without the ".tar.gz" extension.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_metallicities_in_mass_fractions, metallicity_index_by_label};
+///
+/// let index = metallicity_index_by_label("Z0.0140").unwrap();
+/// assert!((get_metallicities_in_mass_fractions()[index] - 0.014).abs() < 1e-8);
+/// assert_eq!(metallicity_index_by_label("Z0_0140"), Some(index));
+/// assert_eq!(metallicity_index_by_label("Z0.014Y0.273"), Some(index));
+/// assert_eq!(metallicity_index_by_label("Z0.0141"), None);
+/// ```
+pub fn metallicity_index_by_label(label: &str) -> Option<usize> {
+    let label = label.trim().replace('_', ".");
+    let track_set = current_track_set();
+    (0..num_metallicities()).find(|&i| {
+        let archive = track_set.archives()[i];
+        track_set.metallicity_names()[i].replace('_', ".") == label
+            || archive == label
+            || archive.strip_suffix(".tar.gz") == Some(label.as_str())
+    })
 }
 
 /// Returns a reference to the array of available masses in units of solar masses.
+/// If a mass range is configured, only the masses within that range are available.
 ///
 /// # Example
 /// ```
@@ -301,8 +1087,129 @@ pub fn get_closest_metallicity_index_from_fe_dex(fe_dex: f64) -> usize {
 ///    println!("Mass in solar masses: {}", mass);
 /// }
 /// ```
-pub fn get_masses_in_solar(metallicity_index: usize) -> &'static [f64] {
-    MASSES[metallicity_index]
+pub fn get_masses_in_solar(metallicity_index: impl Into<MetallicityIndex>) -> &'static [f64] {
+    let metallicity_index = metallicity_index.into().0;
+    &current_track_set().masses()[metallicity_index][current_mass_indices(metallicity_index)]
+}
+
+/// Returns the names of the PARSEC data files the trajectories of a given metallicity are read from.
+/// The file at a given position holds the trajectory with the same mass index, so a suspicious trajectory can be traced back to its source on disk.
+/// If a mass range is configured, only the files of the loaded masses are listed.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_data_filenames, get_masses_in_solar, is_data_ready};
+///
+/// assert!(is_data_ready());
+/// let filenames = get_data_filenames(0);
+/// assert_eq!(filenames.len(), get_masses_in_solar(0).len());
+/// println!("The first trajectory is read from {}", filenames[0]);
+/// ```
+pub fn get_data_filenames(
+    metallicity_index: impl Into<MetallicityIndex>,
+) -> &'static [&'static str] {
+    let metallicity_index = metallicity_index.into().0;
+    &current_track_set().filenames()[metallicity_index][current_mass_indices(metallicity_index)]
+}
+
+/// Returns whether two metallicities share the same mass grid, so that a given mass index refers to the same initial mass in both.
+///
+/// Only then can trajectories of the two metallicities be blended by mass index, as get_interpolated_trajectory() does.
+/// Otherwise they need to be matched by mass value, see common_masses_in_solar().
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::metallicity_grids_aligned;
+///
+/// assert!(metallicity_grids_aligned(3, 3));
+/// ```
+pub fn metallicity_grids_aligned(
+    metallicity_index_a: impl Into<MetallicityIndex>,
+    metallicity_index_b: impl Into<MetallicityIndex>,
+) -> bool {
+    let metallicity_index_a = metallicity_index_a.into().0;
+    let metallicity_index_b = metallicity_index_b.into().0;
+    get_masses_in_solar(metallicity_index_a) == get_masses_in_solar(metallicity_index_b)
+}
+
+/// Returns the masses in units of solar masses that are available for both metallicities.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{common_masses_in_solar, get_masses_in_solar};
+///
+/// let common = common_masses_in_solar(0, 14);
+/// assert!(common.iter().all(|mass| get_masses_in_solar(0).contains(mass)));
+/// assert!(common.iter().all(|mass| get_masses_in_solar(14).contains(mass)));
+/// ```
+pub fn common_masses_in_solar(
+    metallicity_index_a: impl Into<MetallicityIndex>,
+    metallicity_index_b: impl Into<MetallicityIndex>,
+) -> Vec<f64> {
+    let metallicity_index_a = metallicity_index_a.into().0;
+    let metallicity_index_b = metallicity_index_b.into().0;
+    let masses_b = get_masses_in_solar(metallicity_index_b);
+    get_masses_in_solar(metallicity_index_a)
+        .iter()
+        .copied()
+        .filter(|mass| masses_b.contains(mass))
+        .collect()
+}
+
+/// Returns the available masses for a given metallicity as typed quantities.
+/// This allocates a new vector, so prefer get_masses_in_solar() in performance critical code.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_masses, get_masses_in_solar};
+/// use astro_units::mass::solar_mass;
+///
+/// let masses = get_masses(0);
+/// assert_eq!(masses.len(), get_masses_in_solar(0).len());
+/// assert!((masses[1].get::<solar_mass>() / get_masses_in_solar(0)[1] - 1.).abs() < 1e-8);
+/// ```
+pub fn get_masses(metallicity_index: impl Into<MetallicityIndex>) -> Vec<Mass> {
+    let metallicity_index = metallicity_index.into().0;
+    get_masses_in_solar(metallicity_index)
+        .iter()
+        .map(|&mass| Mass::new::<solar_mass>(mass))
+        .collect()
+}
+
+/// Returns the number of available masses for a given metallicity.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_masses_in_solar, num_masses};
+///
+/// assert_eq!(num_masses(0), get_masses_in_solar(0).len());
+/// ```
+pub fn num_masses(metallicity_index: impl Into<MetallicityIndex>) -> usize {
+    let metallicity_index = metallicity_index.into().0;
+    get_masses_in_solar(metallicity_index).len()
+}
+
+/// Iterates over the available masses for a given metallicity, yielding pairs of the mass index and the initial mass in units of solar masses.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_trajectory, is_data_ready, masses};
+/// use astro_units::mass::solar_mass;
+///
+/// assert!(is_data_ready());
+/// for (mass_index, mass_in_solar) in masses(0) {
+///     let initial_mass = get_trajectory(0, mass_index).initial_mass.get::<solar_mass>();
+///     assert!((initial_mass - mass_in_solar).abs() < 1e-2 * mass_in_solar);
+/// }
+/// ```
+pub fn masses(
+    metallicity_index: impl Into<MetallicityIndex>,
+) -> impl Iterator<Item = (usize, f64)> {
+    let metallicity_index = metallicity_index.into().0;
+    get_masses_in_solar(metallicity_index)
+        .iter()
+        .copied()
+        .enumerate()
 }
 
 /// Finds the closest mass enum variant to the given mass in solar masses.
@@ -325,8 +1232,143 @@ pub fn get_masses_in_solar(metallicity_index: usize) -> &'static [f64] {
 /// let expected = get_masses_in_solar(0).len() - 1;
 /// assert_eq!(index, expected);
 /// ```
-pub fn get_closest_mass_index(metallicity_index: usize, mass: Mass) -> usize {
-    get_closest_index(MASSES[metallicity_index], mass.get::<solar_mass>())
+pub fn get_closest_mass_index(metallicity_index: impl Into<MetallicityIndex>, mass: Mass) -> usize {
+    let metallicity_index = metallicity_index.into().0;
+    get_closest_mass_index_from_solar(metallicity_index, mass.get::<solar_mass>())
+}
+
+/// Finds the closest mass enum variant to the given untyped mass in units of solar masses.
+/// This is a convenience for quick scripts, prefer the typed get_closest_mass_index() otherwise.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_mass_index, get_closest_mass_index_from_solar};
+/// use astro_units::mass::solar_mass;
+/// use uom::si::f64::Mass;
+///
+/// let index = get_closest_mass_index_from_solar(0, 1.);
+/// assert_eq!(index, get_closest_mass_index(0, Mass::new::<solar_mass>(1.)));
+/// ```
+pub fn get_closest_mass_index_from_solar(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_in_solar: f64,
+) -> usize {
+    let metallicity_index = metallicity_index.into().0;
+    get_closest_index(get_masses_in_solar(metallicity_index), mass_in_solar)
+}
+
+/// Finds the closest mass enum variant to the given mass, like get_closest_mass_index().
+/// Additionally returns the absolute difference between the provided mass and the chosen grid mass.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::get_closest_mass_index_with_distance;
+/// use astro_units::mass::solar_mass;
+/// use uom::si::f64::Mass;
+///
+/// let (_, distance) = get_closest_mass_index_with_distance(0, Mass::new::<solar_mass>(1.01));
+/// assert!((distance.get::<solar_mass>() - 0.01).abs() < 1e-8);
+/// ```
+pub fn get_closest_mass_index_with_distance(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass: Mass,
+) -> (usize, Mass) {
+    let metallicity_index = metallicity_index.into().0;
+    let (index, distance) = get_closest_index_with_distance(
+        get_masses_in_solar(metallicity_index),
+        mass.get::<solar_mass>(),
+    );
+    (index, Mass::new::<solar_mass>(distance))
+}
+
+/// Finds the closest mass enum variant to the given mass in solar masses, or None if there are no masses for the metallicity.
+///
+/// This is a checked version of get_closest_mass_index(), which also returns None if the metallicity index is out of bounds.
+/// It can therefore be used without calling is_data_ready() first, in which case None is returned if the data could not be loaded.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_mass_index, num_metallicities, try_closest_mass_index};
+/// use astro_units::mass::solar_mass;
+/// use uom::si::f64::Mass;
+///
+/// let mass = Mass::new::<solar_mass>(1.);
+/// if let Some(index) = try_closest_mass_index(0, mass) {
+///     assert_eq!(index, get_closest_mass_index(0, mass));
+/// }
+/// assert_eq!(try_closest_mass_index(num_metallicities(), mass), None);
+/// ```
+pub fn try_closest_mass_index(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass: Mass,
+) -> Option<usize> {
+    let metallicity_index = metallicity_index.into().0;
+    if metallicity_index >= num_metallicities() {
+        return None;
+    }
+    try_closest_index(
+        get_masses_in_solar(metallicity_index),
+        mass.get::<solar_mass>(),
+    )
+}
+
+/// Finds the initial mass of a star with the given metallicity whose lifetime matches the provided value.
+///
+/// The lifetimes of neighbouring masses are searched for the first pair that brackets the requested lifetime, starting from the lowest mass.
+/// Within that pair, the mass is interpolated linearly in log10(mass) over log10(lifetime), so the result is not restricted to the grid.
+/// Lifetimes longer than that of the lowest mass yield the lowest mass, and lifetimes shorter than any other yield the highest mass.
+/// If no trajectories are available for the metallicity, for example because its data could not be loaded, NaN is returned.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{find_mass_for_lifetime, get_trajectory, is_data_ready};
+/// use astro_units::mass::solar_mass;
+///
+/// assert!(is_data_ready());
+/// let trajectory = get_trajectory(1, 30);
+/// let mass = find_mass_for_lifetime(1, trajectory.lifetime);
+/// assert!((mass.get::<solar_mass>() / trajectory.initial_mass.get::<solar_mass>() - 1.).abs() < 0.1);
+/// ```
+pub fn find_mass_for_lifetime(
+    metallicity_index: impl Into<MetallicityIndex>,
+    lifetime: Time,
+) -> Mass {
+    let metallicity_index = metallicity_index.into().0;
+    let data = get_data(metallicity_index);
+    let masses = &data.masses_in_solar;
+    let num_loaded = masses.len().min(data.data.len());
+    if num_loaded == 0 {
+        return Mass::new::<solar_mass>(f64::NAN);
+    }
+    let log_lifetime = |mass_index: usize| data.data[mass_index].lifetime.get::<year>().log10();
+    let target = lifetime.get::<year>().log10();
+
+    for mass_index in 0..num_loaded - 1 {
+        let upper = log_lifetime(mass_index);
+        let lower = log_lifetime(mass_index + 1);
+        if upper >= target && target >= lower {
+            let weight = if upper > lower {
+                (upper - target) / (upper - lower)
+            } else {
+                0.
+            };
+            let lower_mass = masses[mass_index].log10();
+            let upper_mass = masses[mass_index + 1].log10();
+            let mass = 10f64.powf(lower_mass + weight * (upper_mass - lower_mass));
+            return Mass::new::<solar_mass>(mass);
+        }
+    }
+
+    if target > log_lifetime(0) {
+        Mass::new::<solar_mass>(masses[0])
+    } else {
+        Mass::new::<solar_mass>(masses[num_loaded - 1])
+    }
 }
 
 /// Returns a reference to the array of available ages in years.
@@ -353,8 +1395,68 @@ pub fn get_closest_mass_index(metallicity_index: usize, mass: Mass) -> usize {
 ///   }
 /// }
 /// ```
-pub fn get_ages_in_years(metallicity_index: usize, mass_index: usize) -> &'static [f64] {
-    &DATA[metallicity_index].data[mass_index].ages_in_years
+pub fn get_ages_in_years(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+) -> &'static [f64] {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    &get_data(metallicity_index).data[mass_index].ages_in_years
+}
+
+/// Returns the available ages for a given metallicity and mass as typed quantities.
+/// This allocates a new vector, so prefer get_ages_in_years() in performance critical code.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_ages, get_ages_in_years, is_data_ready};
+/// use uom::si::time::year;
+///
+/// assert!(is_data_ready());
+/// let ages = get_ages(0, 0);
+/// assert_eq!(ages.len(), get_ages_in_years(0, 0).len());
+/// assert!((ages[1].get::<year>() / get_ages_in_years(0, 0)[1] - 1.).abs() < 1e-8);
+/// ```
+pub fn get_ages(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+) -> Vec<Time> {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    get_ages_in_years(metallicity_index, mass_index)
+        .iter()
+        .map(|&age| Time::new::<year>(age))
+        .collect()
+}
+
+/// Returns the number of available ages for a given metallicity and mass.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_ages_in_years, is_data_ready, num_ages};
+///
+/// assert!(is_data_ready());
+/// assert_eq!(num_ages(0, 0), get_ages_in_years(0, 0).len());
+/// ```
+pub fn num_ages(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+) -> usize {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    get_data(metallicity_index).data[mass_index]
+        .ages_in_years
+        .len()
 }
 
 /// Finds the closest age enum variant to the given age in years.
@@ -386,16 +1488,325 @@ pub fn get_ages_in_years(metallicity_index: usize, mass_index: usize) -> &'stati
 /// let expected = get_ages_in_years(0, 0).len() - 1;
 /// assert_eq!(index, expected);
 /// ```
-pub fn get_closest_age_index(metallicity_index: usize, mass_index: usize, age: Time) -> usize {
+pub fn get_closest_age_index(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+    age: Time,
+) -> usize {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
     get_closest_index(
-        &DATA[metallicity_index].data[mass_index].ages_in_years,
+        &get_data(metallicity_index).data[mass_index].ages_in_years,
         age.get::<year>(),
     )
 }
 
+/// Finds the model point of a given metallicity that lies closest to the provided position in the Hertzsprung-Russell diagram.
+/// Returns the mass index and the age index of that point.
+///
+/// The distance is measured in the log10(T_eff) / log10(L) plane.
+/// To make the two axes comparable, each of them is normalised by the range it spans across all model points of the metallicity, so the minimised quantity is
+/// d^2 = (delta log10(T_eff) / range of log10(T_eff))^2 + (delta log10(L) / range of log10(L))^2 .
+///
+/// This function iterates over every model point of the metallicity, so it is considerably slower than the index based getters.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{find_closest_by_hr, get_parameters, is_data_ready};
+///
+/// assert!(is_data_ready());
+/// let expected = get_parameters(1, 2, 3);
+/// let (mass_index, age_index) = find_closest_by_hr(1, expected.temperature, expected.luminosity_in_solar);
+/// let found = get_parameters(1, mass_index, age_index);
+/// assert!((found.luminosity_in_solar / expected.luminosity_in_solar - 1.).abs() < 1e-8);
+/// ```
+pub fn find_closest_by_hr(
+    metallicity_index: impl Into<MetallicityIndex>,
+    temperature: ThermodynamicTemperature,
+    luminosity_in_solar: f64,
+) -> (usize, usize) {
+    let metallicity_index = metallicity_index.into().0;
+    let mut closest = (0, 0);
+    let mut min_distance = f64::INFINITY;
+    for (mass_index, age_index, distance) in
+        hr_distances(metallicity_index, temperature, luminosity_in_solar)
+    {
+        if distance < min_distance {
+            min_distance = distance;
+            closest = (mass_index, age_index);
+        }
+    }
+    closest
+}
+
+/// Finds the k model points of a given metallicity that lie closest to the provided position in the Hertzsprung-Russell diagram.
+/// Returns the mass index, the age index and the normalised distance of each point, sorted by ascending distance.
+///
+/// The distance is measured as described for find_closest_by_hr().
+/// If the metallicity has fewer than k model points, all of them are returned.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{find_closest_by_hr, find_k_closest_by_hr, get_parameters, is_data_ready};
+///
+/// assert!(is_data_ready());
+/// let expected = get_parameters(1, 2, 3);
+/// let closest = find_k_closest_by_hr(1, expected.temperature, expected.luminosity_in_solar, 5);
+/// assert_eq!(closest.len(), 5);
+/// assert!(closest.windows(2).all(|pair| pair[0].2 <= pair[1].2));
+/// let (mass_index, age_index) = find_closest_by_hr(1, expected.temperature, expected.luminosity_in_solar);
+/// assert_eq!((closest[0].0, closest[0].1), (mass_index, age_index));
+/// ```
+pub fn find_k_closest_by_hr(
+    metallicity_index: impl Into<MetallicityIndex>,
+    temperature: ThermodynamicTemperature,
+    luminosity_in_solar: f64,
+    k: usize,
+) -> Vec<(usize, usize, f64)> {
+    let metallicity_index = metallicity_index.into().0;
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut points: Vec<(usize, usize, f64)> =
+        hr_distances(metallicity_index, temperature, luminosity_in_solar).collect();
+    let by_distance = |a: &(usize, usize, f64), b: &(usize, usize, f64)| a.2.total_cmp(&b.2);
+    if k < points.len() {
+        points.select_nth_unstable_by(k - 1, by_distance);
+        points.truncate(k);
+    }
+    points.sort_by(by_distance);
+    points
+        .into_iter()
+        .map(|(mass_index, age_index, distance)| (mass_index, age_index, distance.sqrt()))
+        .collect()
+}
+
+/// Iterates over all model points of a metallicity, yielding their mass index, age index and squared normalised distance to the provided position in the Hertzsprung-Russell diagram.
+fn hr_distances(
+    metallicity_index: usize,
+    temperature: ThermodynamicTemperature,
+    luminosity_in_solar: f64,
+) -> impl Iterator<Item = (usize, usize, f64)> {
+    let data = get_data(metallicity_index);
+
+    let mut min_log_te = f64::INFINITY;
+    let mut max_log_te = f64::NEG_INFINITY;
+    let mut min_log_l = f64::INFINITY;
+    let mut max_log_l = f64::NEG_INFINITY;
+    for line in data.data.iter().flat_map(|trajectory| trajectory.iter()) {
+        let (log_te, log_l) = line.hr_coordinates();
+        min_log_te = min_log_te.min(log_te);
+        max_log_te = max_log_te.max(log_te);
+        min_log_l = min_log_l.min(log_l);
+        max_log_l = max_log_l.max(log_l);
+    }
+    let te_range = (max_log_te - min_log_te).max(f64::EPSILON);
+    let l_range = (max_log_l - min_log_l).max(f64::EPSILON);
+
+    let target_log_te = temperature.get::<kelvin>().log10();
+    let target_log_l = luminosity_in_solar.log10();
+    data.data
+        .iter()
+        .enumerate()
+        .flat_map(move |(mass_index, trajectory)| {
+            trajectory.iter().enumerate().map(move |(age_index, line)| {
+                let (log_te, log_l) = line.hr_coordinates();
+                let delta_te = (log_te - target_log_te) / te_range;
+                let delta_l = (log_l - target_log_l) / l_range;
+                (
+                    mass_index,
+                    age_index,
+                    delta_te * delta_te + delta_l * delta_l,
+                )
+            })
+        })
+}
+
+/// Finds the closest age enum variant to the given age, like get_closest_age_index().
+/// Additionally returns the absolute difference between the provided age and the chosen grid age.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_ages_in_years, get_closest_age_index_with_distance, is_data_ready};
+/// use uom::si::{f64::Time, time::year};
+///
+/// assert!(is_data_ready());
+/// let age = get_ages_in_years(0, 0)[3];
+/// let (index, distance) = get_closest_age_index_with_distance(0, 0, Time::new::<year>(age));
+/// assert_eq!(index, 3);
+/// assert!(distance.get::<year>() < 1.);
+/// ```
+pub fn get_closest_age_index_with_distance(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+    age: Time,
+) -> (usize, Time) {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    let (index, distance) = get_closest_index_with_distance(
+        get_ages_in_years(metallicity_index, mass_index),
+        age.get::<year>(),
+    );
+    (index, Time::new::<year>(distance))
+}
+
+/// Finds the closest age enum variant to the given age in years, or None if there are no ages for the trajectory.
+///
+/// This is a checked version of get_closest_age_index(), which also returns None if the metallicity or mass index is out of bounds.
+/// It can therefore be used without calling is_data_ready() first, in which case None is returned if the data could not be loaded.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_closest_age_index, try_closest_age_index};
+/// use astro_units::time::gigayear;
+/// use uom::si::f64::Time;
+///
+/// let age = Time::new::<gigayear>(1.);
+/// if let Some(index) = try_closest_age_index(0, 0, age) {
+///     assert_eq!(index, get_closest_age_index(0, 0, age));
+/// }
+/// assert_eq!(try_closest_age_index(0, 100_000, age), None);
+/// ```
+pub fn try_closest_age_index(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+    age: Time,
+) -> Option<usize> {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    if metallicity_index >= num_metallicities() {
+        return None;
+    }
+    let trajectory = get_data(metallicity_index).data.get(mass_index)?;
+    try_closest_index(&trajectory.ages_in_years, age.get::<year>())
+}
+
+/// Finds the two ages of a trajectory that enclose the given age.
+/// Returns the lower age index, the upper age index, and the fraction of the way from the lower to the upper age, between 0 and 1.
+///
+/// Ages before the first or after the last entry of the trajectory are clamped, in which case both indices are equal and the fraction is 0.
+/// This is the same bracketing that Trajectory::resample() interpolates with.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_age_bracket, get_ages_in_years, is_data_ready};
+/// use uom::si::{f64::Time, time::year};
+///
+/// assert!(is_data_ready());
+///
+/// let ages = get_ages_in_years(0, 0);
+/// let age = (ages[3] + ages[4]) / 2.;
+/// let (lower, upper, fraction) = get_age_bracket(0, 0, Time::new::<year>(age));
+/// assert_eq!((lower, upper), (3, 4));
+/// assert!((fraction - 0.5).abs() < 1e-8);
+///
+/// let (lower, upper, fraction) = get_age_bracket(0, 0, Time::new::<year>(1e15));
+/// assert_eq!((lower, upper, fraction), (ages.len() - 1, ages.len() - 1, 0.));
+/// ```
+pub fn get_age_bracket(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+    age: Time,
+) -> (usize, usize, f64) {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    get_trajectory(metallicity_index, mass_index).age_bracket(age.get::<year>())
+}
+
+/// Finds the largest age index whose age is at or below the given age, which is the floor rather than the nearest index.
+/// This suits step-function integration over the evolution, where a state holds until the next model point is reached.
+///
+/// Ages before the first entry of the trajectory are clamped to index 0, even though that entry lies above the provided age.
+/// Ages after the last entry yield the last index.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_ages_in_years, get_floor_age_index, is_data_ready};
+/// use uom::si::{f64::Time, time::year};
+///
+/// assert!(is_data_ready());
+///
+/// let ages = get_ages_in_years(0, 0);
+/// let age = ages[3] + 0.9 * (ages[4] - ages[3]);
+/// assert_eq!(get_floor_age_index(0, 0, Time::new::<year>(age)), 3);
+/// assert_eq!(get_floor_age_index(0, 0, Time::new::<year>(ages[4])), 4);
+/// assert_eq!(get_floor_age_index(0, 0, Time::new::<year>(0.)), 0);
+/// assert_eq!(get_floor_age_index(0, 0, Time::new::<year>(1e15)), ages.len() - 1);
+/// ```
+pub fn get_floor_age_index(
+    metallicity_index: impl Into<MetallicityIndex>,
+    mass_index: impl Into<MassIndex>,
+    age: Time,
+) -> usize {
+    let metallicity_index = metallicity_index.into().0;
+    let mass_index = mass_index.into().0;
+    get_floor_index(
+        get_ages_in_years(metallicity_index, mass_index),
+        age.get::<year>(),
+    )
+}
+
+fn get_floor_index(list: &[f64], value: f64) -> usize {
+    list.partition_point(|&entry| entry <= value)
+        .saturating_sub(1)
+}
+
+fn try_closest_index(list: &[f64], value: f64) -> Option<usize> {
+    if list.is_empty() {
+        None
+    } else {
+        Some(get_closest_index(list, value))
+    }
+}
+
+fn get_closest_index_with_clamp(list: &[f64], value: f64) -> (usize, Clamp) {
+    let clamp = match (list.first(), list.last()) {
+        (Some(&first), _) if value < first => Clamp::Below,
+        (_, Some(&last)) if value > last => Clamp::Above,
+        _ => Clamp::Within,
+    };
+    (get_closest_index(list, value), clamp)
+}
+
+fn get_closest_index_with_distance(list: &[f64], value: f64) -> (usize, f64) {
+    let index = get_closest_index(list, value);
+    let distance = list
+        .get(index)
+        .map_or(f64::INFINITY, |closest| (value - closest).abs());
+    (index, distance)
+}
+
+/// Returns 0 for an empty list, use try_closest_index() where that case needs to be told apart.
 pub(super) fn get_closest_index(list: &[f64], value: f64) -> usize {
+    let Some(mut max_index) = list.len().checked_sub(1) else {
+        return 0;
+    };
     let mut min_index = 0;
-    let mut max_index = list.len() - 1;
     while max_index - min_index > 1 {
         let mid_index = (max_index + min_index) / 2;
         let mid_mass = list[mid_index];
@@ -411,3 +1822,85 @@ pub(super) fn get_closest_index(list: &[f64], value: f64) -> usize {
         max_index
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid_iterators_enumerate_the_grid() {
+        let expected: Vec<(usize, f64)> = get_metallicities_in_mass_fractions()
+            .iter()
+            .copied()
+            .enumerate()
+            .collect();
+        assert_eq!(metallicities().collect::<Vec<_>>(), expected);
+        for (metallicity_index, _) in metallicities() {
+            let grid: Vec<f64> = masses(metallicity_index).map(|(_, mass)| mass).collect();
+            assert_eq!(grid, get_masses_in_solar(metallicity_index));
+            assert!(masses(metallicity_index)
+                .enumerate()
+                .all(|(position, (mass_index, _))| position == mass_index));
+        }
+    }
+
+    #[test]
+    fn closest_index_of_empty_list_is_none() {
+        assert_eq!(try_closest_index(&[], 1.), None);
+    }
+
+    #[test]
+    fn closest_index_of_empty_list_does_not_panic() {
+        assert_eq!(get_closest_index(&[], 1.), 0);
+        assert_eq!(get_closest_index_with_clamp(&[], 1.).0, 0);
+        assert!(get_closest_index_with_distance(&[], 1.).1.is_infinite());
+    }
+
+    #[test]
+    fn closest_index_of_single_element_is_zero() {
+        assert_eq!(get_closest_index(&[2.], 1.), 0);
+        assert_eq!(get_closest_index(&[2.], 3.), 0);
+    }
+
+    #[test]
+    fn closest_index_with_distance_reports_absolute_difference() {
+        let (index, distance) = get_closest_index_with_distance(&[1., 2., 4.], 2.5);
+        assert_eq!(index, 1);
+        assert!((distance - 0.5).abs() < 1e-8);
+    }
+
+    #[test]
+    fn closest_index_with_clamp_reports_values_outside_of_list() {
+        let list = [1., 2., 4.];
+        assert_eq!(get_closest_index_with_clamp(&list, 0.), (0, Clamp::Below));
+        assert_eq!(get_closest_index_with_clamp(&list, 4.), (2, Clamp::Within));
+        assert_eq!(get_closest_index_with_clamp(&list, 5.), (2, Clamp::Above));
+    }
+
+    #[test]
+    fn unphysical_mass_fractions_are_rejected() {
+        assert!(try_closest_metallicity_index_from_mass_fraction(0.).is_ok());
+        assert!(try_closest_metallicity_index_from_mass_fraction(1.).is_ok());
+        for mass_fraction in [-0.5, 1.5, f64::NAN] {
+            assert!(matches!(
+                try_closest_metallicity_index_from_mass_fraction(mass_fraction),
+                Err(ParsecAccessError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn floor_index_is_clamped_at_both_ends() {
+        let list = [1., 2., 4.];
+        assert_eq!(get_floor_index(&list, 0.), 0);
+        assert_eq!(get_floor_index(&list, 1.), 0);
+        assert_eq!(get_floor_index(&list, 3.9), 1);
+        assert_eq!(get_floor_index(&list, 4.), 2);
+        assert_eq!(get_floor_index(&list, 5.), 2);
+    }
+
+    #[test]
+    fn closest_index_of_single_entry_is_zero() {
+        assert_eq!(try_closest_index(&[2.], 1.), Some(0));
+    }
+}
@@ -0,0 +1,74 @@
+//! Contains the `MetallicityIndex`, `MassIndex` and `AgeIndex` newtypes.
+//!
+//! The getters and the population functions accept either bare usize indices or these newtypes, so existing code keeps working.
+//! Functions that look up an index, such as get_closest_mass_index(), return a bare usize, which converts into the newtypes with `.into()`.
+//! Code that holds on to the newtypes cannot pass a mass index where a metallicity index belongs, because such mix-ups become compile errors.
+//!
+//! # Example
+//! ```
+//! use parsec_access::getters::{get_closest_mass_index, get_trajectory, is_data_ready};
+//! use parsec_access::index::{MassIndex, MetallicityIndex};
+//! use astro_units::mass::solar_mass;
+//! use uom::si::f64::Mass;
+//!
+//! assert!(is_data_ready());
+//! let metallicity = MetallicityIndex(1);
+//! let mass = MassIndex::from(get_closest_mass_index(metallicity, Mass::new::<solar_mass>(1.)));
+//! let trajectory = get_trajectory(metallicity, mass);
+//! assert!((trajectory.initial_mass.get::<solar_mass>() - 1.).abs() < 0.1);
+//! ```
+
+/// The index of a metallicity, accepted wherever a metallicity index is passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MetallicityIndex(pub usize);
+
+/// The index of an initial mass within the grid of a metallicity, accepted wherever a mass index is passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MassIndex(pub usize);
+
+/// The index of an age within a trajectory, accepted wherever an age index is passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AgeIndex(pub usize);
+
+macro_rules! impl_usize_conversions {
+    ($index:ty) => {
+        impl From<usize> for $index {
+            fn from(index: usize) -> Self {
+                Self(index)
+            }
+        }
+
+        impl From<$index> for usize {
+            fn from(index: $index) -> Self {
+                index.0
+            }
+        }
+    };
+}
+
+impl_usize_conversions!(MetallicityIndex);
+impl_usize_conversions!(MassIndex);
+impl_usize_conversions!(AgeIndex);
+
+#[cfg(test)]
+mod test {
+    use crate::getters::{get_masses_in_solar, num_masses};
+
+    use super::*;
+
+    #[test]
+    fn indices_convert_to_and_from_usize() {
+        assert_eq!(MetallicityIndex::from(3), MetallicityIndex(3));
+        assert_eq!(usize::from(MassIndex(4)), 4);
+        assert_eq!(usize::from(AgeIndex::from(5)), 5);
+    }
+
+    #[test]
+    fn getters_accept_newtypes_and_usize_alike() {
+        assert_eq!(num_masses(MetallicityIndex(2)), num_masses(2));
+        assert_eq!(
+            get_masses_in_solar(MetallicityIndex(2)),
+            get_masses_in_solar(2)
+        );
+    }
+}
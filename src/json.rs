@@ -0,0 +1,90 @@
+//! Contains helpers to export PARSEC data as JSON, for example to send it to a web frontend.
+//!
+//! This module is only available with the `json` feature.
+//! Every line is written as an object whose field names state the unit of the value.
+
+use uom::si::{length::kilometer, thermodynamic_temperature::kelvin, time::year};
+
+use astro_units::mass::solar_mass;
+
+use crate::{line::ParsecLine, trajectory::Trajectory};
+
+/// Writes a list of lines, for example an isochrone, as a JSON array of objects.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{get_trajectory, is_data_ready};
+/// use parsec_access::json::lines_to_json;
+///
+/// assert!(is_data_ready());
+/// let zams_lines = vec![get_trajectory(1, 2).zams().clone(), get_trajectory(1, 3).zams().clone()];
+/// let json = lines_to_json(&zams_lines);
+/// assert!(json.starts_with("[{\"mass_in_solar_masses\":"));
+/// ```
+pub fn lines_to_json<'a>(lines: impl IntoIterator<Item = &'a ParsecLine>) -> String {
+    let objects: Vec<String> = lines.into_iter().map(ParsecLine::to_json).collect();
+    format!("[{}]", objects.join(","))
+}
+
+impl ParsecLine {
+    /// Writes the line as a JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"mass_in_solar_masses\":{},\"age_in_years\":{},\"luminosity_in_solar\":{},\"temperature_in_kelvin\":{},\"radius_in_km\":{}}}",
+            number(self.mass.get::<solar_mass>()),
+            number(self.age.get::<year>()),
+            number(self.luminosity_in_solar),
+            number(self.temperature.get::<kelvin>()),
+            number(self.radius.get::<kilometer>()),
+        )
+    }
+}
+
+impl Trajectory {
+    /// Writes the trajectory as a JSON array of objects, one for each line.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let json = get_trajectory(1, 2).to_json();
+    /// assert!(json.starts_with('[') && json.ends_with(']'));
+    /// ```
+    pub fn to_json(&self) -> String {
+        lines_to_json(self.iter())
+    }
+}
+
+/// JSON has no representation for infinite values or NaN, so they are written as null.
+fn number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_is_written_with_unit_labelled_fields() {
+        let line = ParsecLine::sun_like(1e9);
+        let json = lines_to_json([&line, &line]);
+        assert!(
+            json.starts_with("[{\"mass_in_solar_masses\":1,\"age_in_years\":1000000000,"),
+            "{json}"
+        );
+        assert!(json.contains("\"luminosity_in_solar\":1,"), "{json}");
+        assert!(json.contains("},{"), "{json}");
+    }
+
+    #[test]
+    fn non_finite_numbers_are_written_as_null() {
+        assert_eq!(number(f64::NAN), "null");
+        assert_eq!(number(f64::INFINITY), "null");
+        assert_eq!(number(0.5), "0.5");
+    }
+}
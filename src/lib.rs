@@ -3,11 +3,23 @@
 #![doc = include_str!("../README.md")]
 
 mod access;
+pub mod bolometric_correction;
+pub mod config;
+pub mod constants;
 pub mod data;
 pub mod error;
+pub mod events;
 mod file;
 pub mod getters;
+pub mod index;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod line;
+pub mod population;
+pub mod preload;
+pub mod summary;
+#[cfg(feature = "test-data")]
+mod test_data;
 pub mod trajectory;
 
 const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
@@ -1,14 +1,66 @@
 //! Contains the `ParsecLine` struct, which holds the PARSEC data for a given metallicity, initial mass and age.
 
 use astro_units::mass::solar_mass;
+use std::{f64::consts::PI, fmt};
 use uom::si::{
-    f64::{Length, Mass, ThermodynamicTemperature, Time},
-    length::kilometer,
+    angle::radian,
+    f64::{Angle, Length, Mass, MassDensity, Power, ThermodynamicTemperature, Time, Velocity},
+    length::{astronomical_unit, kilometer, meter, parsec},
+    mass::kilogram,
+    power::watt,
     thermodynamic_temperature::kelvin,
-    time::year,
+    time::{day, second, year},
+    velocity::meter_per_second,
 };
 
-use crate::error::ParsecAccessError;
+use crate::{
+    bolometric_correction::BolometricCorrectionTable,
+    constants::{
+        GRAVITATIONAL_CONSTANT_IN_SI, SOLAR_BOLOMETRIC_MAGNITUDE, SOLAR_LUMINOSITY_IN_WATT,
+        SOLAR_RADIUS_IN_KM, SOLAR_TEMPERATURE_IN_KELVIN, SPEED_OF_LIGHT_IN_M_PER_S,
+        STEFAN_BOLTZMANN_CONSTANT_IN_SI,
+    },
+    error::ParsecAccessError,
+};
+
+/// The positions of the columns the parser relies on within a line of a PARSEC data file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ColumnLayout {
+    mass: usize,
+    age: usize,
+    log_l: usize,
+    log_te: usize,
+    log_r: usize,
+}
+
+impl ColumnLayout {
+    /// Locates the columns by their labels in the header line of a PARSEC data file.
+    /// Returns None if any of the labels is missing.
+    pub(crate) fn from_header(header: &str) -> Option<Self> {
+        let labels: Vec<&str> = header.split_whitespace().collect();
+        let position = |label: &str| labels.iter().position(|&entry| entry == label);
+        Some(Self {
+            mass: position("MASS")?,
+            age: position("AGE")?,
+            log_l: position("LOG_L")?,
+            log_te: position("LOG_TE")?,
+            log_r: position("LOG_R")?,
+        })
+    }
+}
+
+impl Default for ColumnLayout {
+    /// The layout of the PARSEC data files at the time of writing, see ParsecLine::column_layout().
+    fn default() -> Self {
+        Self {
+            mass: ParsecLine::MASS_INDEX,
+            age: ParsecLine::AGE_INDEX,
+            log_l: ParsecLine::LOG_L_INDEX,
+            log_te: ParsecLine::LOG_TE_INDEX,
+            log_r: ParsecLine::LOG_R_INDEX,
+        }
+    }
+}
 
 pub(super) struct RawParsecLine {
     mass: f64,
@@ -20,7 +72,7 @@ pub(super) struct RawParsecLine {
 
 /// The data struct holding the PARSEC data for a given metallicity, initial mass and age.
 /// This struct cannot be created directly, but can only be read and accessed through the crate api.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ParsecLine {
     /// The current mass of the star.
     pub mass: Mass,
@@ -32,33 +84,82 @@ pub struct ParsecLine {
     pub temperature: ThermodynamicTemperature,
     /// The current radius of the star.
     pub radius: Length,
+    /// All columns of the line in the PARSEC data file, in the order they appear there.
+    #[cfg(feature = "raw_columns")]
+    pub(crate) raw_columns: Vec<f64>,
 }
 
 impl ParsecLine {
-    const MASS_INDEX: usize = 1;
-    const AGE_INDEX: usize = 2;
-    const LOG_L_INDEX: usize = 3;
-    const LOG_TE_INDEX: usize = 4;
-    const LOG_R_INDEX: usize = 5;
+    /// The index of the column holding the current mass in solar masses, labelled MASS in the PARSEC header.
+    pub const MASS_INDEX: usize = 1;
+    /// The index of the column holding the age in years, labelled AGE in the PARSEC header.
+    pub const AGE_INDEX: usize = 2;
+    /// The index of the column holding log10 of the luminosity in solar units, labelled LOG_L in the PARSEC header.
+    pub const LOG_L_INDEX: usize = 3;
+    /// The index of the column holding log10 of the effective temperature in K, labelled LOG_TE in the PARSEC header.
+    pub const LOG_TE_INDEX: usize = 4;
+    /// The index of the column holding log10 of the radius in cm, labelled LOG_R in the PARSEC header.
+    pub const LOG_R_INDEX: usize = 5;
     pub(crate) const LARGEST_REQUIRED_INDEX: usize = 5;
+    /// Kopparapu et al. (2013) coefficients for the runaway greenhouse limit.
+    const RUNAWAY_GREENHOUSE_COEFFICIENTS: [f64; 5] =
+        [1.0385, 1.2456e-4, 1.4612e-8, -7.6345e-12, -1.7511e-15];
+    /// Kopparapu et al. (2013) coefficients for the maximum greenhouse limit.
+    const MAXIMUM_GREENHOUSE_COEFFICIENTS: [f64; 5] =
+        [0.3507, 5.9578e-5, 1.6707e-9, -3.0058e-12, -5.1925e-16];
+    /// Wright et al. (2018) coefficients for log10 of the convective turnover time in days, as a polynomial in the mass in solar masses.
+    const CONVECTIVE_TURNOVER_COEFFICIENTS: [f64; 3] = [2.33, -1.50, 0.31];
+    /// The electron scattering opacity kappa = 0.2 (1 + X) cm^2/g for a hydrogen mass fraction of X = 0.7.
+    pub const ELECTRON_SCATTERING_OPACITY_IN_CM2_PER_G: f64 = 0.34;
 
+    /// Returns the PARSEC header labels of the columns the parser relies on, together with the column indices it expects them at.
+    /// When reading a data file, the columns are located by these labels in its header, and the indices are only a fallback for files without one.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::line::ParsecLine;
+    ///
+    /// let header = "MODELL MASS AGE LOG_L LOG_TE LOG_R LOG_RAT";
+    /// let labels: Vec<&str> = header.split_whitespace().collect();
+    /// for (label, index) in ParsecLine::column_layout() {
+    ///     assert_eq!(labels[index], label);
+    /// }
+    /// ```
+    pub fn column_layout() -> [(&'static str, usize); 5] {
+        [
+            ("MASS", Self::MASS_INDEX),
+            ("AGE", Self::AGE_INDEX),
+            ("LOG_L", Self::LOG_L_INDEX),
+            ("LOG_TE", Self::LOG_TE_INDEX),
+            ("LOG_R", Self::LOG_R_INDEX),
+        ]
+    }
+
+    #[cfg(test)]
     pub(super) fn read(line: String) -> Result<Self, ParsecAccessError> {
+        Self::read_with_layout(line, &ColumnLayout::default())
+    }
+
+    pub(super) fn read_with_layout(
+        line: String,
+        layout: &ColumnLayout,
+    ) -> Result<Self, ParsecAccessError> {
         let entries: Vec<&str> = line.split_whitespace().collect();
         let mass_entry = entries
-            .get(Self::MASS_INDEX)
+            .get(layout.mass)
             .ok_or(ParsecAccessError::DataNotAvailable("mass".to_string()))?;
 
         let age_entry = entries
-            .get(Self::AGE_INDEX)
+            .get(layout.age)
             .ok_or(ParsecAccessError::DataNotAvailable("age".to_string()))?;
         let log_l_entry = entries
-            .get(Self::LOG_L_INDEX)
+            .get(layout.log_l)
             .ok_or(ParsecAccessError::DataNotAvailable("log_l".to_string()))?;
         let log_te_entry = entries
-            .get(Self::LOG_TE_INDEX)
+            .get(layout.log_te)
             .ok_or(ParsecAccessError::DataNotAvailable("log_te".to_string()))?;
         let log_r_entry = entries
-            .get(Self::LOG_R_INDEX)
+            .get(layout.log_r)
             .ok_or(ParsecAccessError::DataNotAvailable("log_r".to_string()))?;
         if let (Ok(mass), Ok(age), Ok(log_l), Ok(log_te), Ok(log_r)) = (
             mass_entry.parse::<f64>(),
@@ -74,7 +175,15 @@ impl ParsecLine {
                 log_te,
                 log_r,
             }
-            .parse();
+            .parse()?;
+            #[cfg(feature = "raw_columns")]
+            let parsec_line = ParsecLine {
+                raw_columns: entries
+                    .iter()
+                    .map(|entry| entry.parse::<f64>().unwrap_or(f64::NAN))
+                    .collect(),
+                ..parsec_line
+            };
 
             Ok(parsec_line)
         } else {
@@ -83,19 +192,674 @@ impl ParsecLine {
             ))
         }
     }
+
+    /// Returns the value of an arbitrary column of the PARSEC data file, counting from zero.
+    ///
+    /// This is only available with the `raw_columns` feature, which keeps all columns of the data files instead of trimming them.
+    /// Data that has been downloaded without the feature has already been trimmed, so its additional columns are missing.
+    /// None is returned if the column does not exist or could not be parsed as a number.
+    /// Lines that have been interpolated carry no raw columns.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    /// use astro_units::mass::solar_mass;
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// if let Some(mass) = parameters.raw_column(1) {
+    ///     assert!((mass - parameters.mass.get::<solar_mass>()).abs() < 1e-8);
+    /// }
+    /// ```
+    #[cfg(feature = "raw_columns")]
+    pub fn raw_column(&self, index: usize) -> Option<f64> {
+        self.raw_columns
+            .get(index)
+            .copied()
+            .filter(|value| !value.is_nan())
+    }
+
+    /// Compares two lines parameter by parameter, allowing for a relative deviation of up to the given tolerance.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// assert!(parameters.approx_eq(&parameters.clone(), 1e-12));
+    /// assert!(!parameters.approx_eq(get_parameters(1, 2, 4), 1e-12));
+    /// ```
+    pub fn approx_eq(&self, other: &ParsecLine, relative_tolerance: f64) -> bool {
+        let is_close = |a: f64, b: f64| (a - b).abs() <= relative_tolerance * a.abs().max(b.abs());
+        is_close(self.mass.value, other.mass.value)
+            && is_close(self.age.value, other.age.value)
+            && is_close(self.luminosity_in_solar, other.luminosity_in_solar)
+            && is_close(self.temperature.value, other.temperature.value)
+            && is_close(self.radius.value, other.radius.value)
+    }
+
+    /// Returns the mean density of the star, calculated as 3M / (4 pi R^3).
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    /// use uom::si::mass_density::kilogram_per_cubic_meter;
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// assert!(parameters.mean_density().get::<kilogram_per_cubic_meter>() > 0.);
+    /// ```
+    pub fn mean_density(&self) -> MassDensity {
+        mean_density(self.mass, self.radius)
+    }
+
+    /// Returns the mean density of the star in units of the mean density of the sun.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// assert!(parameters.density_in_solar() > 0.);
+    /// ```
+    pub fn density_in_solar(&self) -> f64 {
+        let solar_density = mean_density(
+            Mass::new::<solar_mass>(1.),
+            Length::new::<kilometer>(SOLAR_RADIUS_IN_KM),
+        );
+        (self.mean_density() / solar_density).value
+    }
+
+    /// Returns the escape velocity at the surface of the star, v_esc = sqrt(2 G M / R).
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::constants::SOLAR_METALLICITY_IN_MASS_FRACTION;
+    /// use parsec_access::getters::{get_closest_parameters, is_data_ready};
+    /// use astro_units::{mass::solar_mass, time::gigayear};
+    /// use uom::si::{
+    ///     f64::{Mass, Time},
+    ///     velocity::kilometer_per_second,
+    /// };
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_closest_parameters(SOLAR_METALLICITY_IN_MASS_FRACTION, Mass::new::<solar_mass>(1.), Time::new::<gigayear>(4.6));
+    /// let escape_velocity = parameters.escape_velocity().get::<kilometer_per_second>();
+    /// assert!((escape_velocity - 618.).abs() < 60.);
+    /// ```
+    pub fn escape_velocity(&self) -> Velocity {
+        let mass = self.mass.get::<kilogram>();
+        let radius = self.radius.get::<meter>();
+        let velocity = (2. * GRAVITATIONAL_CONSTANT_IN_SI * mass / radius).sqrt();
+        Velocity::new::<meter_per_second>(velocity)
+    }
+
+    /// Returns the Eddington luminosity of the star, L_Edd = 4 pi G M c / kappa.
+    ///
+    /// The opacity kappa is given in cm^2/g. If none is provided, the electron scattering opacity of a solar-composition envelope is used.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    /// use uom::si::power::watt;
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// let electron_scattering = parameters.eddington_luminosity(None);
+    /// let doubled_opacity = parameters.eddington_luminosity(Some(0.68));
+    /// assert!((electron_scattering.get::<watt>() / doubled_opacity.get::<watt>() - 2.).abs() < 1e-8);
+    /// ```
+    pub fn eddington_luminosity(&self, opacity_in_cm2_per_g: Option<f64>) -> Power {
+        let opacity_in_cm2_per_g =
+            opacity_in_cm2_per_g.unwrap_or(Self::ELECTRON_SCATTERING_OPACITY_IN_CM2_PER_G);
+        let opacity_in_m2_per_kg = opacity_in_cm2_per_g / 10.;
+        let mass = self.mass.get::<kilogram>();
+        let luminosity = 4. * PI * GRAVITATIONAL_CONSTANT_IN_SI * mass * SPEED_OF_LIGHT_IN_M_PER_S
+            / opacity_in_m2_per_kg;
+        Power::new::<watt>(luminosity)
+    }
+
+    /// Returns the ratio L / L_Edd of the luminosity of the star to its Eddington luminosity.
+    ///
+    /// See eddington_luminosity() for the meaning of the opacity argument.
+    /// Ratios approaching 1 indicate phases in which radiation pressure threatens to unbind the envelope.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// assert!(parameters.eddington_ratio(None) > 0.);
+    /// ```
+    pub fn eddington_ratio(&self, opacity_in_cm2_per_g: Option<f64>) -> f64 {
+        let luminosity = self.luminosity_in_solar * SOLAR_LUMINOSITY_IN_WATT;
+        luminosity
+            / self
+                .eddington_luminosity(opacity_in_cm2_per_g)
+                .get::<watt>()
+    }
+
+    /// Returns the luminosity of the star in units of solar luminosities, as computed from its radius and temperature via the Stefan-Boltzmann law, L = 4 pi R^2 sigma T^4.
+    ///
+    /// This is independent of the tabulated luminosity_in_solar, so comparing the two is a consistency check of the PARSEC data.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// let ratio = parameters.luminosity_from_sb() / parameters.luminosity_in_solar;
+    /// assert!((ratio - 1.).abs() < 0.01);
+    /// ```
+    pub fn luminosity_from_sb(&self) -> f64 {
+        let radius = self.radius.get::<meter>();
+        let temperature = self.temperature_in_kelvin();
+        let luminosity =
+            4. * PI * radius * radius * STEFAN_BOLTZMANN_CONSTANT_IN_SI * temperature.powi(4);
+        luminosity / SOLAR_LUMINOSITY_IN_WATT
+    }
+
+    /// Returns an empirical estimate of the convective turnover time of the star, as used to compute Rossby numbers in rotation-activity studies.
+    ///
+    /// The estimate is the relation log10(tau / d) = 2.33 - 1.50 (M / M_sun) + 0.31 (M / M_sun)^2 by Wright et al. (2018), evaluated at the current mass of the star.
+    /// It was calibrated on main-sequence stars between 0.08 and 1.36 solar masses, and is meaningless for more massive or evolved stars.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::constants::SOLAR_METALLICITY_IN_MASS_FRACTION;
+    /// use parsec_access::getters::{get_closest_parameters, is_data_ready};
+    /// use astro_units::{mass::solar_mass, time::gigayear};
+    /// use uom::si::{
+    ///     f64::{Mass, Time},
+    ///     time::day,
+    /// };
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_closest_parameters(SOLAR_METALLICITY_IN_MASS_FRACTION, Mass::new::<solar_mass>(1.), Time::new::<gigayear>(4.6));
+    /// let turnover_time = parameters.convective_turnover_time().get::<day>();
+    /// assert!((turnover_time - 13.8).abs() < 2.);
+    /// ```
+    pub fn convective_turnover_time(&self) -> Time {
+        let mass = self.mass.get::<solar_mass>();
+        let [constant, linear, quadratic] = Self::CONVECTIVE_TURNOVER_COEFFICIENTS;
+        let log_days = constant + linear * mass + quadratic * mass * mass;
+        Time::new::<day>(10f64.powf(log_days))
+    }
+
+    /// Returns the inner and outer boundary of the conservative habitable zone around the star.
+    ///
+    /// The inner boundary is the runaway greenhouse limit, the outer boundary is the maximum greenhouse limit, both taken from Kopparapu et al. (2013).
+    /// The effective stellar flux at each boundary is fitted as a polynomial in (T_eff - 5780 K), and the distance follows as d = sqrt(L / S_eff) AU.
+    /// The fits are only valid for effective temperatures between 2600 K and 7200 K.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    /// use uom::si::length::astronomical_unit;
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// let (inner, outer) = parameters.habitable_zone();
+    /// assert!(inner.get::<astronomical_unit>() < outer.get::<astronomical_unit>());
+    /// ```
+    pub fn habitable_zone(&self) -> (Length, Length) {
+        let inner = self.habitable_zone_boundary(&Self::RUNAWAY_GREENHOUSE_COEFFICIENTS);
+        let outer = self.habitable_zone_boundary(&Self::MAXIMUM_GREENHOUSE_COEFFICIENTS);
+        (inner, outer)
+    }
+
+    /// Returns the angular diameter of the star as seen from the given distance.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    /// use uom::si::{angle::radian, f64::Length, length::parsec};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// let angular_diameter = parameters.angular_diameter(Length::new::<parsec>(10.));
+    /// assert!(angular_diameter.get::<radian>() > 0.);
+    /// ```
+    pub fn angular_diameter(&self, distance: Length) -> Angle {
+        let ratio = (self.radius / distance).value;
+        Angle::new::<radian>(2. * ratio.atan())
+    }
+
+    /// Returns the effective temperature of the star in Kelvin.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    /// use uom::si::thermodynamic_temperature::kelvin;
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// assert_eq!(parameters.temperature_in_kelvin(), parameters.temperature.get::<kelvin>());
+    /// ```
+    pub fn temperature_in_kelvin(&self) -> f64 {
+        self.temperature.get::<kelvin>()
+    }
+
+    /// Returns log10 of the effective temperature of the star in Kelvin, as it is tabulated in the PARSEC data files.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// assert!((10f64.powf(parameters.log_temperature()) / parameters.temperature_in_kelvin() - 1.).abs() < 1e-12);
+    /// ```
+    pub fn log_temperature(&self) -> f64 {
+        self.temperature_in_kelvin().log10()
+    }
+
+    /// Returns the position of the star in the theoretical Hertzsprung-Russell diagram as (log10(T_eff / K), log10(L / L_sun)).
+    ///
+    /// By convention, the temperature axis of the diagram is plotted reversed, with hot stars on the left.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let (log_te, log_l) = get_parameters(1, 2, 3).hr_coordinates();
+    /// assert!(log_te > 3. && log_te < 5.);
+    /// println!("log10(L) = {}", log_l);
+    /// ```
+    pub fn hr_coordinates(&self) -> (f64, f64) {
+        (self.log_temperature(), self.luminosity_in_solar.log10())
+    }
+
+    /// Returns the parameters of the line as plain numbers in SI units, in the order
+    /// (mass in kg, age in s, luminosity in W, effective temperature in K, radius in m).
+    ///
+    /// This flat representation is meant for passing the data across an FFI boundary without depending on uom.
+    /// The luminosity is converted using constants::SOLAR_LUMINOSITY_IN_WATT.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// let (mass_in_kg, _, _, temperature_in_kelvin, _) = parameters.to_si();
+    /// assert!(mass_in_kg > 1e29);
+    /// assert_eq!(temperature_in_kelvin, parameters.temperature_in_kelvin());
+    /// ```
+    pub fn to_si(&self) -> (f64, f64, f64, f64, f64) {
+        (
+            self.mass.get::<kilogram>(),
+            self.age.get::<second>(),
+            self.luminosity_in_solar * SOLAR_LUMINOSITY_IN_WATT,
+            self.temperature_in_kelvin(),
+            self.radius.get::<meter>(),
+        )
+    }
+
+    /// Returns the absolute bolometric magnitude of the star, M_bol = 4.74 - 2.5 log10(L / L_sun).
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::constants::{SOLAR_BOLOMETRIC_MAGNITUDE, SOLAR_METALLICITY_IN_MASS_FRACTION};
+    /// use parsec_access::getters::{get_closest_parameters, is_data_ready};
+    /// use astro_units::{mass::solar_mass, time::gigayear};
+    /// use uom::si::f64::{Mass, Time};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_closest_parameters(SOLAR_METALLICITY_IN_MASS_FRACTION, Mass::new::<solar_mass>(1.), Time::new::<gigayear>(4.6));
+    /// assert!((parameters.bolometric_magnitude() - SOLAR_BOLOMETRIC_MAGNITUDE).abs() < 0.2);
+    /// ```
+    pub fn bolometric_magnitude(&self) -> f64 {
+        SOLAR_BOLOMETRIC_MAGNITUDE - 2.5 * self.luminosity_in_solar.log10()
+    }
+
+    /// Returns log10 of the surface gravity g = G M / R^2 in cgs units, as it is conventionally tabulated.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::constants::SOLAR_METALLICITY_IN_MASS_FRACTION;
+    /// use parsec_access::getters::{get_closest_parameters, is_data_ready};
+    /// use astro_units::{mass::solar_mass, time::gigayear};
+    /// use uom::si::f64::{Mass, Time};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_closest_parameters(SOLAR_METALLICITY_IN_MASS_FRACTION, Mass::new::<solar_mass>(1.), Time::new::<gigayear>(4.6));
+    /// assert!((parameters.log_surface_gravity() - 4.44).abs() < 0.1);
+    /// ```
+    pub fn log_surface_gravity(&self) -> f64 {
+        let mass = self.mass.get::<kilogram>();
+        let radius = self.radius.get::<meter>();
+        let gravity_in_si = GRAVITATIONAL_CONSTANT_IN_SI * mass / (radius * radius);
+        let centimeters_per_meter = 100.;
+        (gravity_in_si * centimeters_per_meter).log10()
+    }
+
+    /// Returns the absolute magnitude of the star in the photometric band described by the provided bolometric correction table.
+    ///
+    /// The bolometric correction is interpolated from the table at the effective temperature and surface gravity of the star, and subtracted from the bolometric magnitude.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::bolometric_correction::BolometricCorrectionTable;
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let table = BolometricCorrectionTable::new(&[(1000., 0., 0.), (1000., 10., 0.), (100000., 0., 0.), (100000., 10., 0.)]).unwrap();
+    /// let parameters = get_parameters(1, 2, 3);
+    /// assert!((parameters.magnitude_in_band(&table) - parameters.bolometric_magnitude()).abs() < 1e-8);
+    /// ```
+    pub fn magnitude_in_band(&self, table: &BolometricCorrectionTable) -> f64 {
+        let bolometric_correction =
+            table.interpolate(self.temperature.get::<kelvin>(), self.log_surface_gravity());
+        self.bolometric_magnitude() - bolometric_correction
+    }
+
+    /// Returns the apparent magnitude of the star as seen from the given distance.
+    ///
+    /// The absolute magnitude in the desired band is obtained by subtracting the provided bolometric correction from the bolometric magnitude, BC = M_bol - M.
+    /// The distance modulus of distance_modulus() is then added on top, neglecting extinction.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_parameters, is_data_ready};
+    /// use uom::si::{f64::Length, length::parsec};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_parameters(1, 2, 3);
+    /// let apparent_magnitude = parameters.apparent_magnitude(Length::new::<parsec>(10.), 0.);
+    /// assert!((apparent_magnitude - parameters.bolometric_magnitude()).abs() < 1e-8);
+    /// ```
+    pub fn apparent_magnitude(&self, distance: Length, bolometric_correction: f64) -> f64 {
+        let absolute_magnitude = self.bolometric_magnitude() - bolometric_correction;
+        absolute_magnitude + distance_modulus(distance)
+    }
+
+    /// Returns an estimate of the B-V colour index of the star in magnitudes.
+    ///
+    /// The estimate inverts the relation of Ballesteros (2012),
+    /// T_eff = 4600 K * (1 / (0.92 (B-V) + 1.7) + 1 / (0.92 (B-V) + 0.62)) ,
+    /// which treats the star as a black body and is fitted to main-sequence stars.
+    /// It is reasonably accurate between roughly 3000 K and 10000 K, and becomes unreliable outside of that range.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::constants::SOLAR_METALLICITY_IN_MASS_FRACTION;
+    /// use parsec_access::getters::{get_closest_parameters, is_data_ready};
+    /// use astro_units::{mass::solar_mass, time::gigayear};
+    /// use uom::si::f64::{Mass, Time};
+    ///
+    /// assert!(is_data_ready());
+    /// let parameters = get_closest_parameters(SOLAR_METALLICITY_IN_MASS_FRACTION, Mass::new::<solar_mass>(1.), Time::new::<gigayear>(4.6));
+    /// assert!((parameters.b_minus_v() - 0.65).abs() < 0.1);
+    /// ```
+    pub fn b_minus_v(&self) -> f64 {
+        // With x = 0.92 (B-V) and tau = T_eff / 4600 K, the relation becomes
+        // tau x^2 + (2.32 tau - 2) x + (1.054 tau - 2.32) = 0 .
+        let tau = self.temperature.get::<kelvin>() / 4600.;
+        let b = 2.32 * tau - 2.;
+        let c = 1.054 * tau - 2.32;
+        let x = (-b + (b * b - 4. * tau * c).sqrt()) / (2. * tau);
+        x / 0.92
+    }
+
+    /// Interpolates between two lines, where a weight of 0 yields the lower and a weight of 1 the upper line.
+    /// Luminosity, temperature and radius are interpolated logarithmically, like they are tabulated by PARSEC.
+    pub(crate) fn interpolate(lower: &ParsecLine, upper: &ParsecLine, weight: f64) -> ParsecLine {
+        let linear = |a: f64, b: f64| a + weight * (b - a);
+        let logarithmic = |a: f64, b: f64| 10f64.powf(linear(a.log10(), b.log10()));
+        ParsecLine {
+            mass: Mass::new::<solar_mass>(linear(
+                lower.mass.get::<solar_mass>(),
+                upper.mass.get::<solar_mass>(),
+            )),
+            age: Time::new::<year>(linear(lower.age.get::<year>(), upper.age.get::<year>())),
+            luminosity_in_solar: logarithmic(lower.luminosity_in_solar, upper.luminosity_in_solar),
+            temperature: ThermodynamicTemperature::new::<kelvin>(logarithmic(
+                lower.temperature.get::<kelvin>(),
+                upper.temperature.get::<kelvin>(),
+            )),
+            radius: Length::new::<kilometer>(logarithmic(
+                lower.radius.get::<kilometer>(),
+                upper.radius.get::<kilometer>(),
+            )),
+            #[cfg(feature = "raw_columns")]
+            raw_columns: Vec::new(),
+        }
+    }
+
+    fn habitable_zone_boundary(&self, coefficients: &[f64; 5]) -> Length {
+        let t = self.temperature.get::<kelvin>() - 5780.;
+        let effective_flux = coefficients
+            .iter()
+            .rev()
+            .fold(0., |acc, coefficient| acc * t + coefficient);
+        let distance = (self.luminosity_in_solar / effective_flux).sqrt();
+        Length::new::<astronomical_unit>(distance)
+    }
+}
+
+impl fmt::Display for ParsecLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "mass: {} solar masses, age: {} years, luminosity: {} solar luminosities, temperature: {} K, radius: {} km",
+            self.mass.get::<solar_mass>(),
+            self.age.get::<year>(),
+            self.luminosity_in_solar,
+            self.temperature.get::<kelvin>(),
+            self.radius.get::<kilometer>()
+        )
+    }
+}
+
+/// Returns the radius a star of the given luminosity and effective temperature must have according to the Stefan-Boltzmann law,
+/// R / R_sun = sqrt(L / L_sun) * (T_sun / T)^2 .
+///
+/// Comparing the result with the radius of a model is a consistency check of the PARSEC data.
+///
+/// # Example
+/// ```
+/// use parsec_access::constants::{SOLAR_RADIUS_IN_KM, SOLAR_TEMPERATURE_IN_KELVIN};
+/// use parsec_access::line::radius_from_luminosity_and_temperature;
+/// use uom::si::{f64::ThermodynamicTemperature, length::kilometer, thermodynamic_temperature::kelvin};
+///
+/// let temperature = ThermodynamicTemperature::new::<kelvin>(SOLAR_TEMPERATURE_IN_KELVIN);
+/// let radius = radius_from_luminosity_and_temperature(1., temperature);
+/// assert!((radius.get::<kilometer>() - SOLAR_RADIUS_IN_KM).abs() < 1e-6 * SOLAR_RADIUS_IN_KM);
+/// ```
+pub fn radius_from_luminosity_and_temperature(
+    luminosity_in_solar: f64,
+    temperature: ThermodynamicTemperature,
+) -> Length {
+    let temperature_in_solar = temperature.get::<kelvin>() / SOLAR_TEMPERATURE_IN_KELVIN;
+    let radius_in_solar =
+        luminosity_in_solar.sqrt() / (temperature_in_solar * temperature_in_solar);
+    Length::new::<kilometer>(radius_in_solar * SOLAR_RADIUS_IN_KM)
+}
+
+/// Returns the distance modulus mu = m - M = 5 log10(d / 10 pc) of an object at the given distance, neglecting extinction.
+///
+/// # Example
+/// ```
+/// use parsec_access::line::distance_modulus;
+/// use uom::si::{f64::Length, length::parsec};
+///
+/// assert!(distance_modulus(Length::new::<parsec>(10.)).abs() < 1e-8);
+/// assert!((distance_modulus(Length::new::<parsec>(1000.)) - 10.).abs() < 1e-8);
+/// ```
+pub fn distance_modulus(distance: Length) -> f64 {
+    5. * (distance.get::<parsec>() / 10.).log10()
+}
+
+fn mean_density(mass: Mass, radius: Length) -> MassDensity {
+    let volume = 4. / 3. * PI * radius * radius * radius;
+    mass / volume
 }
 
 impl RawParsecLine {
-    fn parse(self) -> ParsecLine {
+    fn parse(self) -> Result<ParsecLine, ParsecAccessError> {
         let kelvins = 10f64.powf(self.log_te);
         let centimeters = 10f64.powf(self.log_r);
         let kilometers = centimeters / 1e5;
-        ParsecLine {
+        let is_physical = |value: f64| value.is_finite() && value > 0.;
+        if !is_physical(self.mass) || !is_physical(kelvins) || !is_physical(centimeters) {
+            let message = format!(
+                "Line with mass {} solar masses, temperature {} K and radius {} cm is not physical",
+                self.mass, kelvins, centimeters
+            );
+            return Err(ParsecAccessError::InvalidData(message));
+        }
+        Ok(ParsecLine {
             mass: Mass::new::<solar_mass>(self.mass),
             age: Time::new::<year>(self.age),
             luminosity_in_solar: 10f64.powf(self.log_l),
             temperature: ThermodynamicTemperature::new::<kelvin>(kelvins),
             radius: Length::new::<kilometer>(kilometers),
-        }
+            #[cfg(feature = "raw_columns")]
+            raw_columns: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+impl ParsecLine {
+    /// Returns a line of a sun-like star at the given age, as it would be read from a PARSEC data file.
+    pub(crate) fn sun_like(age_in_years: f64) -> ParsecLine {
+        ParsecLine::read(format!("0 1.0 {age_in_years} 0.0 3.76 10.84"))
+            .expect("the line should be parsable")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_contains_all_parameters_with_units() {
+        let line = ParsecLine::sun_like(1e9);
+        let displayed = line.to_string();
+        assert!(displayed.contains("1 solar masses"), "{displayed}");
+        assert!(displayed.contains("1000000000 years"), "{displayed}");
+        assert!(displayed.contains("1 solar luminosities"), "{displayed}");
+        assert!(displayed.contains(" K"), "{displayed}");
+        assert!(displayed.contains(" km"), "{displayed}");
+    }
+
+    #[test]
+    fn sun_is_far_below_eddington_limit() {
+        let line = ParsecLine::sun_like(4.6e9);
+        let eddington_in_solar =
+            line.eddington_luminosity(None).get::<watt>() / SOLAR_LUMINOSITY_IN_WATT;
+        assert!(
+            (eddington_in_solar - 3.8e4).abs() < 1e3,
+            "{eddington_in_solar}"
+        );
+        assert!((line.eddington_ratio(None) * eddington_in_solar - 1.).abs() < 1e-8);
+    }
+
+    #[test]
+    fn distance_modulus_grows_by_five_per_decade() {
+        let near = distance_modulus(Length::new::<parsec>(100.));
+        let far = distance_modulus(Length::new::<parsec>(1000.));
+        assert!((near - 5.).abs() < 1e-12, "{near}");
+        assert!((far - near - 5.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn stefan_boltzmann_radius_scales_with_luminosity_and_temperature() {
+        let temperature = ThermodynamicTemperature::new::<kelvin>(2. * SOLAR_TEMPERATURE_IN_KELVIN);
+        let radius = radius_from_luminosity_and_temperature(16., temperature);
+        assert!((radius.get::<kilometer>() - SOLAR_RADIUS_IN_KM).abs() < 1e-6 * SOLAR_RADIUS_IN_KM);
+    }
+
+    #[test]
+    fn stefan_boltzmann_luminosity_of_sun_is_one() {
+        let line = ParsecLine {
+            temperature: ThermodynamicTemperature::new::<kelvin>(SOLAR_TEMPERATURE_IN_KELVIN),
+            radius: Length::new::<kilometer>(SOLAR_RADIUS_IN_KM),
+            ..ParsecLine::sun_like(4.6e9)
+        };
+        let luminosity = line.luminosity_from_sb();
+        assert!((luminosity - 1.).abs() < 1e-3, "{luminosity}");
+    }
+
+    #[test]
+    fn convective_turnover_time_of_sun_is_about_two_weeks() {
+        let line = ParsecLine::sun_like(4.6e9);
+        let turnover_time = line.convective_turnover_time().get::<day>();
+        assert!(
+            (turnover_time - 10f64.powf(1.14)).abs() < 1e-8,
+            "{turnover_time}"
+        );
+    }
+
+    #[test]
+    fn si_tuple_contains_sun_in_si_units() {
+        let line = ParsecLine::sun_like(1e9);
+        let (mass, age, luminosity, temperature, radius) = line.to_si();
+        assert!((mass - 1.989e30).abs() < 1e-2 * 1.989e30, "{mass}");
+        assert!((age - 3.156e16).abs() < 1e-3 * 3.156e16, "{age}");
+        assert_eq!(luminosity, SOLAR_LUMINOSITY_IN_WATT);
+        assert!(
+            (temperature - 10f64.powf(3.76)).abs() < 1e-6,
+            "{temperature}"
+        );
+        assert!((radius - 6.9e8).abs() < 0.1 * 6.9e8, "{radius}");
+    }
+
+    #[test]
+    fn log_temperature_reproduces_tabulated_value() {
+        let line = ParsecLine::sun_like(1e9);
+        assert!((line.log_temperature() - 3.76).abs() < 1e-12);
+    }
+
+    #[test]
+    fn lines_are_approximately_equal_within_tolerance() {
+        let line = ParsecLine::sun_like(1e9);
+        let other = ParsecLine::read("0 1.001 1e9 0.0 3.76 10.84".to_string())
+            .expect("the line should be parsable");
+        assert_eq!(line, line.clone());
+        assert_ne!(line, other);
+        assert!(line.approx_eq(&other, 1e-2));
+        assert!(!line.approx_eq(&other, 1e-4));
+    }
+
+    #[test]
+    fn column_layout_is_read_from_header() {
+        let header = "MODELL MASS AGE LOG_L LOG_TE LOG_R LOG_RAT";
+        assert_eq!(
+            ColumnLayout::from_header(header),
+            Some(ColumnLayout::default())
+        );
+        let reordered = ColumnLayout::from_header("MODELL AGE MASS LOG_R LOG_TE LOG_L")
+            .expect("all labels are present");
+        let line = ParsecLine::read_with_layout("0 1e9 1.0 10.84 3.76 0.0".to_string(), &reordered)
+            .expect("the line should be parsable");
+        let expected = ParsecLine::sun_like(1e9);
+        assert_eq!(line.mass, expected.mass);
+        assert_eq!(line.age, expected.age);
+        assert_eq!(line.luminosity_in_solar, expected.luminosity_in_solar);
+        assert_eq!(line.temperature, expected.temperature);
+        assert_eq!(line.radius, expected.radius);
+        #[cfg(feature = "raw_columns")]
+        assert_eq!(line.raw_columns, vec![0.0, 1e9, 1.0, 10.84, 3.76, 0.0]);
+        assert_eq!(ColumnLayout::from_header("MODELL MASS AGE"), None);
+    }
+
+    #[test]
+    fn line_with_negative_mass_is_rejected() {
+        let result = ParsecLine::read("0 -1.0 1e9 0.0 3.76 10.84".to_string());
+        assert!(matches!(result, Err(ParsecAccessError::InvalidData(_))));
+    }
+
+    #[test]
+    fn line_with_infinite_radius_is_rejected() {
+        let result = ParsecLine::read("0 1.0 1e9 0.0 3.76 1e10".to_string());
+        assert!(matches!(result, Err(ParsecAccessError::InvalidData(_))));
     }
 }
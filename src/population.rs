@@ -0,0 +1,289 @@
+//! Contains functions that combine the trajectories of a metallicity into properties of a whole stellar population, such as isochrones, integrated parameters and colour-magnitude diagrams.
+
+use rayon::prelude::*;
+use uom::si::{
+    f64::{Mass, ThermodynamicTemperature, Time},
+    thermodynamic_temperature::kelvin,
+    time::year,
+};
+
+use crate::{
+    getters::{get_masses_in_solar, get_trajectory},
+    index::MetallicityIndex,
+    line::ParsecLine,
+};
+
+/// The integrated properties of a stellar population of a single age and metallicity.
+#[derive(Clone, Debug)]
+pub struct IntegratedResult {
+    /// The summed luminosity of all surviving stars in units of solar luminosities.
+    pub total_luminosity_in_solar: f64,
+    /// The effective temperature averaged over all surviving stars, weighted by their luminosity.
+    pub mean_temperature: ThermodynamicTemperature,
+    /// The number of surviving stars.
+    pub number_of_stars: f64,
+}
+
+/// Integrates the parameters of a population of stars with the given metallicity and age over an initial mass function.
+///
+/// The initial mass function maps an initial mass in solar masses to the number of stars per unit solar mass, dN/dm.
+/// Its normalisation carries over to the result, so normalising it to the number of stars in a cluster yields the total luminosity of that cluster.
+///
+/// The population is sampled at the initial masses of the grid. Each mass represents the bin reaching halfway to its neighbours,
+/// and is weighted by the initial mass function times the width of that bin. Stars whose lifetime is shorter than the age are considered dead and do not contribute.
+/// The parameters of the surviving stars are interpolated at the requested age.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::is_data_ready;
+/// use parsec_access::population::integrated_parameters;
+/// use astro_units::time::gigayear;
+/// use uom::si::{f64::Time, thermodynamic_temperature::kelvin};
+///
+/// assert!(is_data_ready());
+/// let salpeter = |mass_in_solar: f64| mass_in_solar.powf(-2.35);
+/// let young = integrated_parameters(8, Time::new::<gigayear>(0.1), &salpeter);
+/// let old = integrated_parameters(8, Time::new::<gigayear>(10.), &salpeter);
+/// assert!(young.total_luminosity_in_solar > old.total_luminosity_in_solar);
+/// assert!(young.mean_temperature > old.mean_temperature);
+/// ```
+pub fn integrated_parameters(
+    metallicity_index: impl Into<MetallicityIndex>,
+    age: Time,
+    imf: &dyn Fn(f64) -> f64,
+) -> IntegratedResult {
+    let metallicity_index = metallicity_index.into().0;
+    let masses = get_masses_in_solar(metallicity_index);
+    let mut number_of_stars = 0.;
+    let mut total_luminosity_in_solar = 0.;
+    let mut weighted_temperature_sum = 0.;
+    let widths = bin_widths(masses);
+    for (mass_index, parameters) in isochrone(metallicity_index, age) {
+        let number = imf(masses[mass_index]) * widths[mass_index];
+        let luminosity = number * parameters.luminosity_in_solar;
+        number_of_stars += number;
+        total_luminosity_in_solar += luminosity;
+        weighted_temperature_sum += luminosity * parameters.temperature.get::<kelvin>();
+    }
+    let mean_temperature = if total_luminosity_in_solar > 0. {
+        weighted_temperature_sum / total_luminosity_in_solar
+    } else {
+        0.
+    };
+    IntegratedResult {
+        total_luminosity_in_solar,
+        mean_temperature: ThermodynamicTemperature::new::<kelvin>(mean_temperature),
+        number_of_stars,
+    }
+}
+
+/// Creates an approximate colour-magnitude diagram of a population of stars with the given metallicity and age.
+/// Returns a (colour, absolute magnitude) point for every initial mass of the grid that is still alive at that age.
+///
+/// The colour of a star is given by color_fn, and its absolute magnitude is its bolometric magnitude minus the bolometric correction given by bc_fn.
+/// For a quick start, see get_default_cmd().
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::bolometric_correction::BolometricCorrectionTable;
+/// use parsec_access::getters::is_data_ready;
+/// use parsec_access::line::ParsecLine;
+/// use parsec_access::population::get_cmd;
+/// use astro_units::time::gigayear;
+/// use uom::si::f64::Time;
+///
+/// assert!(is_data_ready());
+/// let table = BolometricCorrectionTable::new(&[(1000., 0., -0.1), (1000., 10., -0.1), (100000., 0., -0.1), (100000., 10., -0.1)]).unwrap();
+/// let cmd = get_cmd(8, Time::new::<gigayear>(1.), &ParsecLine::b_minus_v, &|line| {
+///     table.interpolate(line.temperature_in_kelvin(), line.log_surface_gravity())
+/// });
+/// assert!(!cmd.is_empty());
+/// ```
+pub fn get_cmd(
+    metallicity_index: impl Into<MetallicityIndex>,
+    age: Time,
+    color_fn: &dyn Fn(&ParsecLine) -> f64,
+    bc_fn: &dyn Fn(&ParsecLine) -> f64,
+) -> Vec<(f64, f64)> {
+    let metallicity_index = metallicity_index.into().0;
+    isochrone(metallicity_index, age)
+        .map(|(_, line)| (color_fn(&line), line.bolometric_magnitude() - bc_fn(&line)))
+        .collect()
+}
+
+/// Creates an approximate colour-magnitude diagram like get_cmd(), using the B-V colour of ParsecLine::b_minus_v() and a bolometric correction of zero.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::is_data_ready;
+/// use parsec_access::population::get_default_cmd;
+/// use astro_units::time::gigayear;
+/// use uom::si::f64::Time;
+///
+/// assert!(is_data_ready());
+/// for (b_minus_v, magnitude) in get_default_cmd(8, Time::new::<gigayear>(1.)) {
+///     println!("B-V = {:.2}, M = {:.2}", b_minus_v, magnitude);
+/// }
+/// ```
+pub fn get_default_cmd(
+    metallicity_index: impl Into<MetallicityIndex>,
+    age: Time,
+) -> Vec<(f64, f64)> {
+    let metallicity_index = metallicity_index.into().0;
+    get_cmd(metallicity_index, age, &ParsecLine::b_minus_v, &|_| 0.)
+}
+
+/// Returns the isochrone of the metallicity at the given age, which holds the interpolated parameters of every initial mass of the grid that is still alive at that age.
+/// The lines are ordered by initial mass.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::is_data_ready;
+/// use parsec_access::population::get_isochrone;
+/// use astro_units::time::gigayear;
+/// use uom::si::f64::Time;
+///
+/// assert!(is_data_ready());
+/// let young = get_isochrone(8, Time::new::<gigayear>(0.1));
+/// let old = get_isochrone(8, Time::new::<gigayear>(10.));
+/// assert!(young.len() > old.len());
+/// ```
+pub fn get_isochrone(metallicity_index: impl Into<MetallicityIndex>, age: Time) -> Vec<ParsecLine> {
+    let metallicity_index = metallicity_index.into().0;
+    isochrone(metallicity_index, age)
+        .map(|(_, line)| line)
+        .collect()
+}
+
+/// Returns the isochrones of the metallicity at each of the given ages, like get_isochrone().
+/// The isochrones are computed in parallel, and are returned in the order of the ages.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::is_data_ready;
+/// use parsec_access::population::{get_isochrone, get_isochrones};
+/// use astro_units::time::gigayear;
+/// use uom::si::f64::Time;
+///
+/// assert!(is_data_ready());
+/// let ages: Vec<Time> = (1..=100).map(|i| Time::new::<gigayear>(0.1 * i as f64)).collect();
+/// let isochrones = get_isochrones(8, &ages);
+/// assert_eq!(isochrones.len(), ages.len());
+/// assert_eq!(isochrones[42], get_isochrone(8, ages[42]));
+/// ```
+pub fn get_isochrones(
+    metallicity_index: impl Into<MetallicityIndex>,
+    ages: &[Time],
+) -> Vec<Vec<ParsecLine>> {
+    let metallicity_index = metallicity_index.into().0;
+    ages.par_iter()
+        .map(|&age| get_isochrone(metallicity_index, age))
+        .collect()
+}
+
+/// Returns the initial mass of every star of the metallicity, paired with the age at which it reaches the main-sequence turnoff.
+/// Read in reverse, this is the turnoff mass as a function of the age of a cluster.
+///
+/// The turnoff is located by Trajectory::turnoff_index(). Stars that do not leave the main sequence within their trajectory are omitted.
+/// The result is ordered by initial mass.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::is_data_ready;
+/// use parsec_access::population::turnoff_ages;
+/// use astro_units::mass::solar_mass;
+/// use uom::si::time::year;
+///
+/// assert!(is_data_ready());
+/// for (mass, age) in turnoff_ages(8) {
+///     println!("{} solar masses: turnoff after {} years", mass.get::<solar_mass>(), age.get::<year>());
+/// }
+/// ```
+pub fn turnoff_ages(metallicity_index: impl Into<MetallicityIndex>) -> Vec<(Mass, Time)> {
+    let metallicity_index = metallicity_index.into().0;
+    (0..get_masses_in_solar(metallicity_index).len())
+        .filter_map(|mass_index| {
+            let trajectory = get_trajectory(metallicity_index, mass_index);
+            let turnoff = trajectory.turnoff_parameters()?;
+            Some((trajectory.initial_mass, turnoff.age))
+        })
+        .collect()
+}
+
+/// Yields the mass index and the interpolated parameters of every star of the metallicity that is still alive at the given age.
+fn isochrone(metallicity_index: usize, age: Time) -> impl Iterator<Item = (usize, ParsecLine)> {
+    let age_in_years = age.get::<year>();
+    (0..get_masses_in_solar(metallicity_index).len()).filter_map(move |mass_index| {
+        let trajectory = get_trajectory(metallicity_index, mass_index);
+        trajectory
+            .is_alive_at(age)
+            .then(|| (mass_index, trajectory.interpolate_at_year(age_in_years)))
+    })
+}
+
+/// Returns the width of the mass bin represented by each mass, reaching halfway to the neighbouring masses.
+/// The outermost bins end at the outermost masses.
+fn bin_widths(masses: &[f64]) -> Vec<f64> {
+    (0..masses.len())
+        .map(|i| {
+            let lower = if i == 0 {
+                masses[0]
+            } else {
+                (masses[i - 1] + masses[i]) / 2.
+            };
+            let upper = match masses.get(i + 1) {
+                Some(next) => (masses[i] + next) / 2.,
+                None => masses[i],
+            };
+            upper - lower
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bin_widths_add_up_to_mass_range() {
+        let masses = [0.5, 1., 2., 4.];
+        let widths = bin_widths(&masses);
+        assert_eq!(widths, vec![0.25, 0.75, 1.5, 1.]);
+        assert!((widths.iter().sum::<f64>() - 3.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bin_widths_of_empty_grid_are_empty() {
+        assert!(bin_widths(&[]).is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+//! Contains the `PreloadHandle` struct, which allows loading PARSEC data on a background thread.
+
+use std::thread::{self, JoinHandle};
+
+use crate::{data::ParsecData, error::ParsecAccessError, file::ensure_data_files};
+
+/// A handle to data that is being downloaded and loaded on a background thread, as started by spawn_preload().
+pub struct PreloadHandle {
+    thread: JoinHandle<Result<(), ParsecAccessError>>,
+}
+
+impl PreloadHandle {
+    /// Returns whether the background thread has finished, successfully or not.
+    /// This does not block, so it can be polled from a user interface thread.
+    pub fn is_done(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Blocks until the background thread has finished, and returns whether the data could be loaded.
+    pub fn join(self) -> Result<(), ParsecAccessError> {
+        self.thread.join().map_err(|_| {
+            ParsecAccessError::Other("The thread preloading the PARSEC data panicked".to_string())
+        })?
+    }
+}
+
+/// Downloads and loads the data of the given metallicities on a background thread.
+///
+/// Interactive applications can use this to prepare the data without blocking their main thread.
+/// Once the returned handle reports success, the getters can be used for these metallicities without delay.
+///
+/// # Example
+/// ```
+/// use parsec_access::preload::spawn_preload;
+///
+/// let handle = spawn_preload(vec![0, 1]);
+/// while !handle.is_done() {
+///     std::thread::sleep(std::time::Duration::from_millis(10));
+/// }
+/// assert!(handle.join().is_ok());
+/// ```
+pub fn spawn_preload(metallicity_indices: Vec<usize>) -> PreloadHandle {
+    let thread = thread::spawn(move || preload(&metallicity_indices));
+    PreloadHandle { thread }
+}
+
+/// Downloads and loads the data of the given metallicities on the blocking thread pool of the provided tokio runtime, like spawn_preload().
+///
+/// Services that already manage a tokio runtime can use this to keep the work on their executor, instead of having the crate spawn a thread of its own.
+///
+/// # Example
+/// ```
+/// use parsec_access::preload::spawn_preload_on;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let task = spawn_preload_on(runtime.handle(), vec![0, 1]);
+/// assert!(runtime.block_on(task).unwrap().is_ok());
+/// ```
+#[cfg(feature = "tokio")]
+pub fn spawn_preload_on(
+    handle: &tokio::runtime::Handle,
+    metallicity_indices: Vec<usize>,
+) -> tokio::task::JoinHandle<Result<(), ParsecAccessError>> {
+    handle.spawn_blocking(move || preload(&metallicity_indices))
+}
+
+fn preload(metallicity_indices: &[usize]) -> Result<(), ParsecAccessError> {
+    for &metallicity_index in metallicity_indices {
+        ensure_data_files(metallicity_index)?;
+    }
+    for &metallicity_index in metallicity_indices {
+        if !ParsecData::loaded(metallicity_index).is_valid() {
+            let message = format!("Parsec Data for metallicity index {metallicity_index}");
+            return Err(ParsecAccessError::DataNotAvailable(message));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,182 @@
+//! Contains the `GridSummary` struct, which gives an overview over the available PARSEC data,
+//! and the lightweight `GridManifest` struct, which only lists the available metallicities.
+
+use std::fmt;
+
+use crate::{
+    getters::{get_data, get_masses_in_solar, get_metallicities_relative_to_solar, metallicities},
+    PACKAGE_VERSION,
+};
+
+/// An overview over the available data of a single metallicity.
+#[derive(Clone, Debug)]
+pub struct MetallicitySummary {
+    /// The metallicity in units of mass fraction Z.
+    pub mass_fraction: f64,
+    /// The metallicity in dex for the element iron, [Fe/H].
+    pub fe_dex: f64,
+    /// The smallest available initial mass in units of solar masses.
+    pub min_mass_in_solar: f64,
+    /// The largest available initial mass in units of solar masses.
+    pub max_mass_in_solar: f64,
+    /// The number of available initial masses.
+    pub num_masses: usize,
+    /// The number of age samples, summed over all trajectories.
+    pub num_age_samples: usize,
+}
+
+/// An overview over the available data of all metallicities, as returned by grid_summary().
+/// Its Display implementation produces a readable table.
+#[derive(Clone, Debug)]
+pub struct GridSummary {
+    /// The summaries of the individual metallicities, ordered like the metallicity indices.
+    pub metallicities: Vec<MetallicitySummary>,
+}
+
+/// Collects an overview over the available data of all metallicities.
+///
+/// # Safety
+///
+/// This function does not perform any out-of-bounds checks.
+/// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::{is_data_ready, num_metallicities};
+/// use parsec_access::summary::grid_summary;
+///
+/// assert!(is_data_ready());
+/// let summary = grid_summary();
+/// assert_eq!(summary.metallicities.len(), num_metallicities());
+/// println!("{}", summary);
+/// ```
+pub fn grid_summary() -> GridSummary {
+    let fe_dex = get_metallicities_relative_to_solar();
+    let metallicities = metallicities()
+        .map(|(metallicity_index, mass_fraction)| {
+            let masses = get_masses_in_solar(metallicity_index);
+            let num_age_samples = get_data(metallicity_index)
+                .data
+                .iter()
+                .map(|trajectory| trajectory.ages_in_years.len())
+                .sum();
+            MetallicitySummary {
+                mass_fraction,
+                fe_dex: fe_dex[metallicity_index],
+                min_mass_in_solar: masses.first().copied().unwrap_or(f64::NAN),
+                max_mass_in_solar: masses.last().copied().unwrap_or(f64::NAN),
+                num_masses: masses.len(),
+                num_age_samples,
+            }
+        })
+        .collect();
+    GridSummary { metallicities }
+}
+
+/// A metallicity listed in a GridManifest.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestEntry {
+    /// The metallicity in units of mass fraction Z.
+    pub mass_fraction: f64,
+    /// The metallicity in dex for the element iron, [Fe/H].
+    pub fe_dex: f64,
+}
+
+/// A lightweight list of the available metallicities, as returned by grid_manifest().
+///
+/// In contrast to GridSummary, it does not touch any trajectories, so it is cheap to produce and to ship.
+/// With the `serde` feature, it implements Serialize and Deserialize, for example to write a manifest file.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridManifest {
+    /// The version of the crate that produced the manifest.
+    pub version: String,
+    /// The available metallicities, ordered like the metallicity indices.
+    pub metallicities: Vec<ManifestEntry>,
+}
+
+/// Lists the available metallicities without loading any data.
+///
+/// # Example
+/// ```
+/// use parsec_access::getters::num_metallicities;
+/// use parsec_access::summary::grid_manifest;
+///
+/// let manifest = grid_manifest();
+/// assert_eq!(manifest.metallicities.len(), num_metallicities());
+/// ```
+pub fn grid_manifest() -> GridManifest {
+    let fe_dex = get_metallicities_relative_to_solar();
+    let metallicities = metallicities()
+        .map(|(metallicity_index, mass_fraction)| ManifestEntry {
+            mass_fraction,
+            fe_dex: fe_dex[metallicity_index],
+        })
+        .collect();
+    GridManifest {
+        version: PACKAGE_VERSION.to_string(),
+        metallicities,
+    }
+}
+
+impl fmt::Display for GridSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} metallicities", self.metallicities.len())?;
+        writeln!(
+            f,
+            "{:>5} {:>8} {:>7} {:>21} {:>7} {:>9}",
+            "index", "Z", "[Fe/H]", "masses in M_sun", "masses", "ages"
+        )?;
+        for (index, metallicity) in self.metallicities.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>5} {:>8.4} {:>7.2} {:>10.2} - {:>8.2} {:>7} {:>9}",
+                index,
+                metallicity.mass_fraction,
+                metallicity.fe_dex,
+                metallicity.min_mass_in_solar,
+                metallicity.max_mass_in_solar,
+                metallicity.num_masses,
+                metallicity.num_age_samples
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn manifest_lists_metallicities_in_order() {
+        let manifest = grid_manifest();
+        assert_eq!(manifest.version, PACKAGE_VERSION);
+        assert!(!manifest.metallicities.is_empty());
+        assert!(manifest
+            .metallicities
+            .windows(2)
+            .all(|pair| pair[0].mass_fraction < pair[1].mass_fraction));
+    }
+
+    #[test]
+    fn display_lists_every_metallicity() {
+        let metallicity = MetallicitySummary {
+            mass_fraction: 0.014,
+            fe_dex: 0.06,
+            min_mass_in_solar: 0.1,
+            max_mass_in_solar: 350.,
+            num_masses: 100,
+            num_age_samples: 20_000,
+        };
+        let summary = GridSummary {
+            metallicities: vec![metallicity.clone(), metallicity],
+        };
+        let displayed = summary.to_string();
+        assert!(displayed.starts_with("2 metallicities\n"), "{displayed}");
+        assert_eq!(displayed.lines().count(), 4, "{displayed}");
+        assert!(displayed.contains("0.0140"), "{displayed}");
+        assert!(displayed.contains("350.00"), "{displayed}");
+    }
+}
@@ -0,0 +1,89 @@
+//! Contains the small subset of tracks that is embedded with the `test-data` feature, and selected with TrackSet::TestData.
+//!
+//! The tracks are synthetic and merely follow the layout of the PARSEC data files.
+//! They roughly resemble main sequence stars and obey the Stefan-Boltzmann law, which suffices to exercise the crate offline, but they are no substitute for the actual data.
+
+pub(crate) static METALLICITIES_IN_MASS_FRACTION: [f64; 2] = [0.004, 0.014];
+
+pub(crate) static METALLICITIES_IN_DEX: [f64; 2] = [-0.4842998393467859, 0.059768205003489776];
+
+pub(crate) static METALLICITY_NAMES: [&str; 2] = ["Z0_0040", "Z0_0140"];
+
+pub(crate) static METALLICITY_ARCHIVES: [&str; 2] = ["Z0.004Y0.256.tar.gz", "Z0.014Y0.273.tar.gz"];
+
+const SUBSET_MASSES: [f64; 3] = [0.8, 1.0, 2.0];
+
+pub(crate) static MASSES: [&[f64]; 2] = [&SUBSET_MASSES, &SUBSET_MASSES];
+
+pub(crate) static FILENAMES: [&[&str]; 2] = [
+    &[
+        "Z0.004Y0.256OUTA1.77_F7_M000.800.DAT",
+        "Z0.004Y0.256OUTA1.77_F7_M001.000.DAT",
+        "Z0.004Y0.256OUTA1.77_F7_M002.000.DAT",
+    ],
+    &[
+        "Z0.014Y0.273OUTA1.77_F7_M000.800.DAT",
+        "Z0.014Y0.273OUTA1.77_F7_M001.000.DAT",
+        "Z0.014Y0.273OUTA1.77_F7_M002.000.DAT",
+    ],
+];
+
+/// The content of the data files, ordered like FILENAMES.
+pub(crate) static FILE_CONTENTS: [&[&str]; 2] = [
+    &[
+        include_str!("test_data/Z0.004Y0.256/Z0.004Y0.256OUTA1.77_F7_M000.800.DAT"),
+        include_str!("test_data/Z0.004Y0.256/Z0.004Y0.256OUTA1.77_F7_M001.000.DAT"),
+        include_str!("test_data/Z0.004Y0.256/Z0.004Y0.256OUTA1.77_F7_M002.000.DAT"),
+    ],
+    &[
+        include_str!("test_data/Z0.014Y0.273/Z0.014Y0.273OUTA1.77_F7_M000.800.DAT"),
+        include_str!("test_data/Z0.014Y0.273/Z0.014Y0.273OUTA1.77_F7_M001.000.DAT"),
+        include_str!("test_data/Z0.014Y0.273/Z0.014Y0.273OUTA1.77_F7_M002.000.DAT"),
+    ],
+];
+
+#[cfg(test)]
+mod test {
+    use astro_units::mass::solar_mass;
+
+    use crate::{constants::SOLAR_METALLICITY_IN_MASS_FRACTION, data::ParsecData};
+
+    use super::*;
+
+    #[test]
+    fn embedded_tables_are_consistent() {
+        for (metallicity_index, &mass_fraction) in METALLICITIES_IN_MASS_FRACTION.iter().enumerate()
+        {
+            let dex = (mass_fraction / SOLAR_METALLICITY_IN_MASS_FRACTION).log10();
+            assert!((METALLICITIES_IN_DEX[metallicity_index] - dex).abs() < 1e-12);
+            assert_eq!(
+                FILENAMES[metallicity_index].len(),
+                MASSES[metallicity_index].len()
+            );
+            assert_eq!(
+                FILE_CONTENTS[metallicity_index].len(),
+                MASSES[metallicity_index].len()
+            );
+        }
+    }
+
+    #[test]
+    fn embedded_files_are_parsable() {
+        for (metallicity_index, &mass_fraction) in METALLICITIES_IN_MASS_FRACTION.iter().enumerate()
+        {
+            let readers = FILE_CONTENTS[metallicity_index]
+                .iter()
+                .map(|content| content.as_bytes());
+            let data = ParsecData::from_readers(
+                mass_fraction,
+                MASSES[metallicity_index].to_vec(),
+                readers,
+            )
+            .expect("the embedded data should be parsable");
+            for (mass_index, &mass) in MASSES[metallicity_index].iter().enumerate() {
+                let initial_mass = data[mass_index].initial_mass.get::<solar_mass>();
+                assert!((initial_mass / mass - 1.).abs() < 1e-2, "{initial_mass}");
+            }
+        }
+    }
+}
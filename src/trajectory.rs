@@ -8,7 +8,10 @@ use uom::si::{
     time::year,
 };
 
-use super::line::ParsecLine;
+use super::{error::ParsecAccessError, line::ParsecLine};
+
+/// Stars below this initial mass develop a degenerate helium core, which ignites in a flash at the tip of the red giant branch.
+const DEGENERATE_CORE_MASS_LIMIT_IN_SOLAR: f64 = 2.;
 
 /// The data struct holding the PARSEC data for a given metallicity and initial mass.
 /// This struct cannot be created directly, but can only be read and accessed through the crate api.
@@ -52,18 +55,777 @@ impl Trajectory {
         }
     }
 
-    pub(super) fn is_empty(&self) -> bool {
+    /// Returns the parameters of the star on the zero-age main sequence, which is the first entry of the trajectory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trajectory is empty.
+    /// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    /// use uom::si::{length::kilometer, thermodynamic_temperature::kelvin};
+    ///
+    /// assert!(is_data_ready());
+    /// let zams = get_trajectory(1, 2).zams();
+    /// println!("Luminosity at ZAMS: {} sol", zams.luminosity_in_solar);
+    /// println!("Temperature at ZAMS: {} K", zams.temperature.get::<kelvin>());
+    /// println!("Radius at ZAMS: {} km", zams.radius.get::<kilometer>());
+    /// ```
+    pub fn zams(&self) -> &ParsecLine {
+        &self.params[0]
+    }
+
+    /// Returns the index and the parameters of the hottest point of the trajectory.
+    /// If several points share the highest effective temperature, the first of them is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trajectory is empty.
+    /// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let (index, hottest) = trajectory.peak_temperature();
+    /// assert!(hottest.temperature >= trajectory.zams().temperature);
+    /// assert_eq!(hottest.age, trajectory[index].age);
+    /// ```
+    pub fn peak_temperature(&self) -> (usize, &ParsecLine) {
+        first_maximum(&self.params, |line| line.temperature.value)
+    }
+
+    /// Returns the index and the parameters of the most luminous point of the trajectory.
+    /// If several points share the highest luminosity, the first of them is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trajectory is empty.
+    /// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let (_, brightest) = trajectory.peak_luminosity();
+    /// assert!(brightest.luminosity_in_solar >= trajectory.zams().luminosity_in_solar);
+    /// ```
+    pub fn peak_luminosity(&self) -> (usize, &ParsecLine) {
+        first_maximum(&self.params, |line| line.luminosity_in_solar)
+    }
+
+    /// Locates the main-sequence turnoff and returns its index, or None if the star does not leave the main sequence within the trajectory.
+    ///
+    /// This is a heuristic: the star is considered to have left the main sequence once its radius exceeds five times its radius on the zero-age main sequence,
+    /// and the turnoff is taken to be the hottest point before that. For massive stars, which cool during their whole main-sequence evolution,
+    /// this is the hottest point of the main sequence rather than the onset of the contraction at its end.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_closest_trajectory, is_data_ready};
+    /// use astro_units::mass::solar_mass;
+    /// use uom::si::f64::Mass;
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_closest_trajectory(0.0122, Mass::new::<solar_mass>(1.));
+    /// let turnoff_index = trajectory.turnoff_index().unwrap();
+    /// assert!(trajectory[turnoff_index].temperature >= trajectory.zams().temperature);
+    /// ```
+    pub fn turnoff_index(&self) -> Option<usize> {
+        const GIANT_RADIUS_FACTOR: f64 = 5.;
+
+        let zams_radius = self.params.first()?.radius;
+        let giant_index = self
+            .params
+            .iter()
+            .position(|line| line.radius > GIANT_RADIUS_FACTOR * zams_radius)?;
+        let (turnoff_index, _) =
+            first_maximum(&self.params[..giant_index], |line| line.temperature.value);
+        Some(turnoff_index)
+    }
+
+    /// Returns the parameters at the main-sequence turnoff, or None if the star does not leave the main sequence within the trajectory.
+    /// See turnoff_index() for how the turnoff is located.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_closest_trajectory, is_data_ready};
+    /// use astro_units::mass::solar_mass;
+    /// use uom::si::{f64::Mass, thermodynamic_temperature::kelvin};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_closest_trajectory(0.0122, Mass::new::<solar_mass>(1.));
+    /// if let Some(turnoff) = trajectory.turnoff_parameters() {
+    ///     println!("Turnoff temperature: {} K", turnoff.temperature.get::<kelvin>());
+    /// }
+    /// ```
+    pub fn turnoff_parameters(&self) -> Option<&ParsecLine> {
+        self.turnoff_index().map(|index| &self.params[index])
+    }
+
+    /// Locates the tip of the red giant branch, the luminosity maximum a low-mass star reaches right before helium ignites in its degenerate core.
+    /// Returns the index and the parameters of that point, or None if the star does not go through a red giant branch within the trajectory.
+    ///
+    /// This is a heuristic:
+    /// - Only stars with an initial mass below 2 solar masses develop a degenerate helium core, so more massive stars yield None.
+    /// - The candidate tip is the most luminous point of the trajectory, and the main-sequence turnoff is taken to be the hottest point before it.
+    /// - The candidate is accepted if it is at least 50 times as luminous as the turnoff, which is far more than a star gains on the main sequence.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_closest_trajectory, is_data_ready};
+    /// use astro_units::mass::solar_mass;
+    /// use uom::si::f64::Mass;
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_closest_trajectory(0.0122, Mass::new::<solar_mass>(1.));
+    /// if let Some((_, tip)) = trajectory.rgb_tip() {
+    ///     println!("The RGB tip is reached at {} solar luminosities", tip.luminosity_in_solar);
+    /// }
+    /// ```
+    pub fn rgb_tip(&self) -> Option<(usize, &ParsecLine)> {
+        const MINIMUM_LUMINOSITY_RATIO: f64 = 50.;
+
+        if self.is_empty()
+            || self.initial_mass.get::<solar_mass>() >= DEGENERATE_CORE_MASS_LIMIT_IN_SOLAR
+        {
+            return None;
+        }
+        let (tip_index, tip) = self.peak_luminosity();
+        let (_, turnoff) = first_maximum(&self.params[..=tip_index], |line| line.temperature.value);
+        if tip.luminosity_in_solar >= MINIMUM_LUMINOSITY_RATIO * turnoff.luminosity_in_solar {
+            Some((tip_index, tip))
+        } else {
+            None
+        }
+    }
+
+    /// Locates the blue loop, the excursion towards higher temperatures that intermediate-mass stars perform in the HR diagram after igniting helium in their core.
+    /// Returns the age indices at which the loop starts and ends, or None if the star does not perform a blue loop within the trajectory.
+    ///
+    /// This is a heuristic over the sequence of effective temperatures:
+    /// - Stars below 2 solar masses ignite helium in a flash at the tip of the red giant branch instead, so they yield None.
+    /// - Starting at the main-sequence turnoff (see turnoff_index()), the star cools down as a giant. The loop starts at the coolest point before the temperature rises again.
+    /// - The rise only counts as a loop if the temperature increases by at least 10 percent over that coolest point, which ignores numerical wiggles along the giant branch.
+    /// - The loop ends at the first point after that rise where the temperature has fallen back to the one at the start of the loop.
+    ///   If the trajectory ends before, the loop is incomplete and None is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_closest_trajectory, is_data_ready};
+    /// use astro_units::mass::solar_mass;
+    /// use uom::si::{f64::Mass, time::year};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_closest_trajectory(0.0122, Mass::new::<solar_mass>(5.));
+    /// if let Some((start, end)) = trajectory.blue_loop_extent() {
+    ///     let duration = trajectory[end].age - trajectory[start].age;
+    ///     println!("The blue loop lasts {} years", duration.get::<year>());
+    /// }
+    /// ```
+    pub fn blue_loop_extent(&self) -> Option<(usize, usize)> {
+        const MINIMUM_TEMPERATURE_RATIO: f64 = 1.1;
+
+        if self.initial_mass.get::<solar_mass>() < DEGENERATE_CORE_MASS_LIMIT_IN_SOLAR {
+            return None;
+        }
+        let temperatures: Vec<f64> = self
+            .params
+            .iter()
+            .map(|line| line.temperature.value)
+            .collect();
+        let mut start = self.turnoff_index()?;
+        let mut rise = None;
+        for (index, &temperature) in temperatures.iter().enumerate().skip(start) {
+            if temperature < temperatures[start] {
+                start = index;
+            } else if temperature >= MINIMUM_TEMPERATURE_RATIO * temperatures[start] {
+                rise = Some(index);
+                break;
+            }
+        }
+        let end = temperatures
+            .iter()
+            .enumerate()
+            .skip(rise?)
+            .find(|(_, &temperature)| temperature <= temperatures[start])
+            .map(|(index, _)| index)?;
+        Some((start, end))
+    }
+
+    /// Returns whether the star is still alive at the given age, meaning that the age does not exceed its lifetime.
+    ///
+    /// Querying parameters beyond the lifetime yields the clamped last entry, which does not describe the remnant.
+    /// This check allows skipping such stars instead.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// assert!(trajectory.is_alive_at(trajectory.lifetime));
+    /// assert!(!trajectory.is_alive_at(2. * trajectory.lifetime));
+    /// ```
+    pub fn is_alive_at(&self, age: Time) -> bool {
+        age <= self.lifetime
+    }
+
+    /// Returns the fraction of the initial mass the star has lost up to the given age index, 1 - M / M_initial.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// assert!(trajectory.mass_lost_fraction_at(0).abs() < 1e-8);
+    /// ```
+    pub fn mass_lost_fraction_at(&self, age_index: usize) -> f64 {
+        1. - (self.params[age_index].mass / self.initial_mass).value
+    }
+
+    /// Returns the fraction of the initial mass the star has lost by the end of the trajectory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trajectory is empty.
+    /// Call is_data_ready() once before using this function to ensure that the data is loaded and valid.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let total_mass_lost = get_trajectory(1, 2).total_mass_lost();
+    /// assert!((0. ..1.).contains(&total_mass_lost));
+    /// ```
+    pub fn total_mass_lost(&self) -> f64 {
+        self.mass_lost_fraction_at(self.params.len() - 1)
+    }
+
+    /// Returns the age of the first and the last entry of the trajectory, which bound the ages the trajectory can be queried at.
+    /// For an empty trajectory, both are zero.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let (first_age, last_age) = trajectory.age_range();
+    /// assert!(first_age < last_age);
+    /// assert_eq!(last_age, trajectory.lifetime);
+    /// ```
+    pub fn age_range(&self) -> (Time, Time) {
+        let first_age = self.ages_in_years.first().copied().unwrap_or(0.);
+        (Time::new::<year>(first_age), self.lifetime)
+    }
+
+    /// Resamples the trajectory at the provided ages in years.
+    ///
+    /// Every parameter is interpolated between the two neighbouring entries of the trajectory.
+    /// Ages before the first or after the last entry are clamped, so they yield the first or last entry, respectively.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    /// use uom::si::time::year;
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let lifetime = trajectory.lifetime.get::<year>();
+    /// let ages: Vec<f64> = (0..=100).map(|i| i as f64 / 100. * lifetime).collect();
+    /// let resampled = trajectory.resample(&ages);
+    /// assert_eq!(resampled.len(), ages.len());
+    /// ```
+    pub fn resample(&self, ages_in_years: &[f64]) -> Vec<ParsecLine> {
+        ages_in_years
+            .iter()
+            .map(|&age| self.interpolate_at_year(age))
+            .collect()
+    }
+
+    /// Resamples the trajectory at n logarithmically spaced ages, from the first nonzero age up to the lifetime.
+    ///
+    /// Stellar evolution is fast early on and slow later, so logarithmic spacing resolves both regimes.
+    /// Because a logarithmic scale cannot start at zero, an entry at age zero is skipped and the sampling starts at the next entry.
+    /// An empty trajectory, or one without any positive age, yields an empty result.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let resampled = trajectory.resample_log(100);
+    /// assert_eq!(resampled.len(), 100);
+    /// assert!(((resampled[99].age / trajectory.lifetime).value - 1.).abs() < 1e-8);
+    /// ```
+    pub fn resample_log(&self, n: usize) -> Vec<ParsecLine> {
+        let Some(&first_age) = self.ages_in_years.iter().find(|&&age| age > 0.) else {
+            return Vec::new();
+        };
+        let last_age = self.lifetime.get::<year>();
+        self.resample(&log_spaced(first_age, last_age, n))
+    }
+
+    /// Resamples this and the other trajectory at common ages, so that they can be compared point by point, for example across metallicities.
+    /// Returns the resampled lines of this trajectory and of the other one, in that order.
+    ///
+    /// The common ages are the union of the ages of both trajectories, restricted to the range both of them cover.
+    /// Restricting the range means that neither trajectory is clamped, at the price of dropping the youngest and oldest ages that only one of them reaches.
+    /// If the trajectories do not overlap in age, both results are empty.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let (low_metallicity, high_metallicity) = get_trajectory(1, 30).align_with(get_trajectory(12, 30));
+    /// assert_eq!(low_metallicity.len(), high_metallicity.len());
+    /// for (low, high) in low_metallicity.iter().zip(high_metallicity.iter()) {
+    ///     assert_eq!(low.age, high.age);
+    /// }
+    /// ```
+    pub fn align_with(&self, other: &Trajectory) -> (Vec<ParsecLine>, Vec<ParsecLine>) {
+        let (Some(&first), Some(&other_first)) =
+            (self.ages_in_years.first(), other.ages_in_years.first())
+        else {
+            return (Vec::new(), Vec::new());
+        };
+        let start = first.max(other_first);
+        let end = self
+            .lifetime
+            .get::<year>()
+            .min(other.lifetime.get::<year>());
+        let mut ages: Vec<f64> = self
+            .ages_in_years
+            .iter()
+            .chain(other.ages_in_years.iter())
+            .copied()
+            .filter(|age| (start..=end).contains(age))
+            .collect();
+        ages.sort_by(f64::total_cmp);
+        ages.dedup();
+        (self.resample(&ages), other.resample(&ages))
+    }
+
+    /// Returns the current mass of the star in solar masses at each entry of the trajectory, analogous to ages_in_years.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let masses = trajectory.current_masses_in_solar();
+    /// assert_eq!(masses.len(), trajectory.ages_in_years.len());
+    /// ```
+    pub fn current_masses_in_solar(&self) -> Vec<f64> {
+        self.params
+            .iter()
+            .map(|line| line.mass.get::<solar_mass>())
+            .collect()
+    }
+
+    /// Returns the luminosity of the star in solar luminosities at each entry of the trajectory, analogous to ages_in_years.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let luminosities = trajectory.luminosities_in_solar();
+    /// assert_eq!(luminosities.len(), trajectory.ages_in_years.len());
+    /// ```
+    pub fn luminosities_in_solar(&self) -> Vec<f64> {
+        self.params
+            .iter()
+            .map(|line| line.luminosity_in_solar)
+            .collect()
+    }
+
+    /// Returns the evolutionary track in the theoretical Hertzsprung-Russell diagram, see ParsecLine::hr_coordinates() for the convention.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let track = trajectory.hr_track();
+    /// assert_eq!(track.len(), trajectory.ages_in_years.len());
+    /// ```
+    pub fn hr_track(&self) -> Vec<(f64, f64)> {
+        self.params.iter().map(ParsecLine::hr_coordinates).collect()
+    }
+
+    /// Returns the parameters at each of the provided ages, interpolated like in resample().
+    ///
+    /// The ages must be sorted in ascending order. The trajectory and the ages are then walked through together,
+    /// which is considerably faster than searching every age independently when the list is long.
+    /// For unsorted input the results are meaningless.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    /// use uom::si::{f64::Time, time::year};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// let lifetime = trajectory.lifetime.get::<year>();
+    /// let ages: Vec<Time> = (0..1000).map(|i| Time::new::<year>(i as f64 / 1000. * lifetime)).collect();
+    /// let parameters = trajectory.parameters_at_ages(&ages);
+    /// assert_eq!(parameters.len(), ages.len());
+    /// ```
+    pub fn parameters_at_ages(&self, sorted_ages: &[Time]) -> Vec<ParsecLine> {
+        let mut upper_index = 0;
+        sorted_ages
+            .iter()
+            .map(|age| {
+                let age = age.get::<year>();
+                while upper_index < self.ages_in_years.len()
+                    && self.ages_in_years[upper_index] < age
+                {
+                    upper_index += 1;
+                }
+                self.interpolate_with_bracket(self.bracket_from_upper_index(upper_index, age))
+            })
+            .collect()
+    }
+
+    /// Returns the parameters at the given age, interpolated like in resample().
+    /// Instead of clamping, ages outside of the trajectory are rejected with an error, because the star does not exist at that age.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// assert!(trajectory.interpolate_checked(trajectory.lifetime / 2.).is_ok());
+    /// assert!(trajectory.interpolate_checked(trajectory.lifetime * 2.).is_err());
+    /// ```
+    pub fn interpolate_checked(&self, age: Time) -> Result<ParsecLine, ParsecAccessError> {
+        let (first_age, last_age) = self.age_range();
+        if self.is_empty() || age < first_age || age > last_age {
+            let message = format!(
+                "Age {} years lies outside of the trajectory, which covers {} to {} years",
+                age.get::<year>(),
+                first_age.get::<year>(),
+                last_age.get::<year>()
+            );
+            return Err(ParsecAccessError::OutOfRange(message));
+        }
+        Ok(self.interpolate_at_year(age.get::<year>()))
+    }
+
+    pub(crate) fn interpolate_at_year(&self, age: f64) -> ParsecLine {
+        self.interpolate_with_bracket(self.age_bracket(age))
+    }
+
+    fn interpolate_with_bracket(&self, bracket: (usize, usize, f64)) -> ParsecLine {
+        let (lower_index, upper_index, weight) = bracket;
+        if lower_index == upper_index {
+            return self.params[lower_index].clone();
+        }
+        ParsecLine::interpolate(&self.params[lower_index], &self.params[upper_index], weight)
+    }
+
+    /// Returns the indices of the ages enclosing the given age in years, and the fraction of the way from the lower to the upper one.
+    /// Ages outside of the trajectory are clamped to its ends.
+    pub(crate) fn age_bracket(&self, age: f64) -> (usize, usize, f64) {
+        let upper_index = self.ages_in_years.partition_point(|&a| a < age);
+        self.bracket_from_upper_index(upper_index, age)
+    }
+
+    /// Completes the bracket for an age, given the index of the first entry that is not younger.
+    fn bracket_from_upper_index(&self, upper_index: usize, age: f64) -> (usize, usize, f64) {
+        if upper_index == 0 {
+            return (0, 0, 0.);
+        }
+        if upper_index >= self.ages_in_years.len() {
+            let last_index = self.ages_in_years.len() - 1;
+            return (last_index, last_index, 0.);
+        }
+        let lower_index = upper_index - 1;
+        let lower_age = self.ages_in_years[lower_index];
+        let upper_age = self.ages_in_years[upper_index];
+        let weight = (age - lower_age) / (upper_age - lower_age);
+        (lower_index, upper_index, weight)
+    }
+
+    /// Returns the number of lines, which is the number of age samples, of the trajectory.
+    ///
+    /// # Example
+    /// ```
+    /// use parsec_access::getters::{get_trajectory, is_data_ready};
+    ///
+    /// assert!(is_data_ready());
+    /// let trajectory = get_trajectory(1, 2);
+    /// assert_eq!(trajectory.len(), trajectory.ages_in_years.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Returns whether the trajectory contains no lines.
+    pub fn is_empty(&self) -> bool {
         self.params.is_empty()
     }
+
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, ParsecLine> {
+        self.params.iter()
+    }
+}
+
+/// Returns n values between the positive start and end, inclusively, that are equally spaced on a logarithmic scale.
+fn log_spaced(start: f64, end: f64, n: usize) -> Vec<f64> {
+    if n == 1 {
+        return vec![start];
+    }
+    let ratio = end / start;
+    (0..n)
+        .map(|i| start * ratio.powf(i as f64 / (n - 1) as f64))
+        .collect()
+}
+
+/// Returns the index and the line with the largest key, preferring the first of several equal ones.
+fn first_maximum(lines: &[ParsecLine], key: impl Fn(&ParsecLine) -> f64) -> (usize, &ParsecLine) {
+    let mut peak_index = 0;
+    for (index, line) in lines.iter().enumerate() {
+        if key(line) > key(&lines[peak_index]) {
+            peak_index = index;
+        }
+    }
+    (peak_index, &lines[peak_index])
 }
 
 #[cfg(test)]
 mod test {
-    use super::Trajectory;
+    use astro_units::mass::solar_mass;
+    use uom::si::{
+        f64::{Length, Mass, ThermodynamicTemperature, Time},
+        length::kilometer,
+        thermodynamic_temperature::kelvin,
+        time::year,
+    };
+
+    use super::{log_spaced, ParsecAccessError, ParsecLine, Trajectory};
+
+    fn line(age_in_years: f64, luminosity_in_solar: f64) -> ParsecLine {
+        ParsecLine {
+            luminosity_in_solar,
+            ..ParsecLine::sun_like(age_in_years)
+        }
+    }
+
+    #[test]
+    fn aligned_trajectories_share_ages_within_common_range() {
+        let trajectory = Trajectory::new(vec![line(0., 1.), line(10., 2.), line(20., 3.)]);
+        let other = Trajectory::new(vec![line(5., 1.), line(15., 2.), line(30., 3.)]);
+        let (aligned, other_aligned) = trajectory.align_with(&other);
+        let ages: Vec<f64> = aligned.iter().map(|line| line.age.get::<year>()).collect();
+        let other_ages: Vec<f64> = other_aligned
+            .iter()
+            .map(|line| line.age.get::<year>())
+            .collect();
+        assert_eq!(ages, vec![5., 10., 15., 20.]);
+        assert_eq!(other_ages, ages);
+        assert!((aligned[0].luminosity_in_solar - 2f64.sqrt()).abs() < 1e-12);
+
+        let disjoint = Trajectory::new(vec![line(100., 1.), line(200., 2.)]);
+        let (aligned, other_aligned) = trajectory.align_with(&disjoint);
+        assert!(aligned.is_empty() && other_aligned.is_empty());
+    }
+
+    #[test]
+    fn columns_are_extracted_in_order() {
+        let trajectory = Trajectory::new(vec![line(0., 1.), line(10., 2.)]);
+        assert_eq!(trajectory.current_masses_in_solar(), vec![1., 1.]);
+        assert_eq!(trajectory.luminosities_in_solar(), vec![1., 2.]);
+    }
+
+    #[test]
+    fn log_spaced_ages_have_constant_ratio() {
+        let ages = log_spaced(1e2, 1e6, 5);
+        let expected = [1e2, 1e3, 1e4, 1e5, 1e6];
+        for (age, expected) in ages.iter().zip(expected) {
+            assert!((age / expected - 1.).abs() < 1e-12, "{age}");
+        }
+        assert_eq!(log_spaced(1e2, 1e6, 1), vec![1e2]);
+        assert!(log_spaced(1e2, 1e6, 0).is_empty());
+    }
+
+    #[test]
+    fn log_resampling_skips_zero_age() {
+        let trajectory = Trajectory::new(vec![line(0., 1.), line(10., 2.), line(1000., 3.)]);
+        let resampled = trajectory.resample_log(3);
+        let ages: Vec<f64> = resampled
+            .iter()
+            .map(|line| line.age.get::<year>())
+            .collect();
+        assert!((ages[0] - 10.).abs() < 1e-9, "{ages:?}");
+        assert!((ages[1] - 100.).abs() < 1e-9, "{ages:?}");
+        assert!((ages[2] - 1000.).abs() < 1e-9, "{ages:?}");
+        assert!(Trajectory::new(vec![]).resample_log(3).is_empty());
+    }
 
     #[test]
     fn constructor_with_empty_params_does_not_throw() {
         let trajectory = Trajectory::new(vec![]);
         assert!(trajectory.is_empty());
     }
+
+    #[test]
+    fn age_range_spans_first_to_last_age() {
+        let trajectory = Trajectory::new(vec![line(1., 1.), line(3., 1.)]);
+        let (first_age, last_age) = trajectory.age_range();
+        assert_eq!(first_age.get::<year>(), 1.);
+        assert_eq!(last_age.get::<year>(), 3.);
+        let (first_age, last_age) = Trajectory::new(vec![]).age_range();
+        assert_eq!((first_age.get::<year>(), last_age.get::<year>()), (0., 0.));
+    }
+
+    #[test]
+    fn peak_luminosity_returns_first_maximum() {
+        let trajectory =
+            Trajectory::new(vec![line(1., 1.), line(2., 5.), line(3., 5.), line(4., 2.)]);
+        let (index, brightest) = trajectory.peak_luminosity();
+        assert_eq!(index, 1);
+        assert_eq!(brightest.luminosity_in_solar, 5.);
+    }
+
+    #[test]
+    fn rgb_tip_is_luminosity_maximum_after_turnoff() {
+        let mut main_sequence = line(1., 1.);
+        main_sequence.temperature = ThermodynamicTemperature::new::<kelvin>(5800.);
+        let mut turnoff = line(2., 2.);
+        turnoff.temperature = ThermodynamicTemperature::new::<kelvin>(6000.);
+        let giant = line(3., 2000.);
+        let horizontal_branch = line(4., 50.);
+        let trajectory = Trajectory::new(vec![main_sequence, turnoff, giant, horizontal_branch]);
+        let (index, tip) = trajectory.rgb_tip().expect("the trajectory has an RGB tip");
+        assert_eq!(index, 2);
+        assert_eq!(tip.luminosity_in_solar, 2000.);
+    }
+
+    #[test]
+    fn turnoff_is_hottest_point_before_becoming_giant() {
+        let temperatures = [5800., 6000., 5900., 4500.];
+        let radii = [700_000., 800_000., 1_000_000., 10_000_000.];
+        let lines = (0..4)
+            .map(|i| {
+                let mut line = line(i as f64, 1.);
+                line.temperature = ThermodynamicTemperature::new::<kelvin>(temperatures[i]);
+                line.radius = Length::new::<kilometer>(radii[i]);
+                line
+            })
+            .collect();
+        let trajectory = Trajectory::new(lines);
+        assert_eq!(trajectory.turnoff_index(), Some(1));
+        assert!(Trajectory::new(vec![line(1., 1.), line(2., 1.)])
+            .turnoff_parameters()
+            .is_none());
+    }
+
+    fn intermediate_mass_track(temperatures: &[f64]) -> Trajectory {
+        let lines = temperatures
+            .iter()
+            .enumerate()
+            .map(|(i, &temperature)| {
+                let mut line = line(i as f64, 100.);
+                line.mass = Mass::new::<solar_mass>(5.);
+                line.temperature = ThermodynamicTemperature::new::<kelvin>(temperature);
+                // The star becomes a giant right after the turnoff at index 1.
+                let radius = if i < 2 { 2_000_000. } else { 50_000_000. };
+                line.radius = Length::new::<kilometer>(radius);
+                line
+            })
+            .collect();
+        Trajectory::new(lines)
+    }
+
+    #[test]
+    fn blue_loop_spans_from_red_giant_back_to_its_temperature() {
+        let trajectory = intermediate_mass_track(&[
+            15000., 16000., 6000., 4200., 4000., 5500., 7000., 4500., 3900., 3500.,
+        ]);
+        assert_eq!(trajectory.blue_loop_extent(), Some((4, 8)));
+    }
+
+    #[test]
+    fn small_wiggles_and_incomplete_loops_are_no_blue_loops() {
+        let wiggle = intermediate_mass_track(&[15000., 16000., 6000., 4000., 4100., 3900., 3500.]);
+        assert_eq!(wiggle.blue_loop_extent(), None);
+        let incomplete = intermediate_mass_track(&[15000., 16000., 6000., 4000., 5500., 7000.]);
+        assert_eq!(incomplete.blue_loop_extent(), None);
+    }
+
+    #[test]
+    fn main_sequence_star_has_no_rgb_tip() {
+        let trajectory = Trajectory::new(vec![line(1., 1.), line(2., 1.5)]);
+        assert!(trajectory.rgb_tip().is_none());
+    }
+
+    #[test]
+    fn mass_lost_fraction_compares_to_initial_mass() {
+        let mut late = line(2., 1.);
+        late.mass = Mass::new::<solar_mass>(0.75);
+        let trajectory = Trajectory::new(vec![line(1., 1.), late]);
+        assert_eq!(trajectory.mass_lost_fraction_at(0), 0.);
+        assert!((trajectory.total_mass_lost() - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn checked_interpolation_rejects_ages_outside_of_trajectory() {
+        let trajectory = Trajectory::new(vec![line(1., 1.), line(3., 100.)]);
+        assert!(trajectory
+            .interpolate_checked(Time::new::<year>(2.))
+            .is_ok());
+        for age in [0., 4.] {
+            assert!(matches!(
+                trajectory.interpolate_checked(Time::new::<year>(age)),
+                Err(ParsecAccessError::OutOfRange(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn resampling_interpolates_and_clamps() {
+        let trajectory = Trajectory::new(vec![line(1., 1.), line(3., 100.)]);
+        let resampled = trajectory.resample(&[0., 2., 4.]);
+        assert!((resampled[0].luminosity_in_solar - 1.).abs() < 1e-8);
+        assert!((resampled[1].age.get::<year>() - 2.).abs() < 1e-8);
+        assert!((resampled[1].luminosity_in_solar - 10.).abs() < 1e-8);
+        assert!((resampled[2].luminosity_in_solar - 100.).abs() < 1e-8);
+    }
+
+    #[test]
+    fn age_bracket_encloses_age_and_clamps_at_ends() {
+        let trajectory = Trajectory::new(vec![line(1., 1.), line(3., 1.), line(7., 1.)]);
+        assert_eq!(trajectory.age_bracket(4.), (1, 2, 0.25));
+        assert_eq!(trajectory.age_bracket(3.), (0, 1, 1.));
+        assert_eq!(trajectory.age_bracket(0.), (0, 0, 0.));
+        assert_eq!(trajectory.age_bracket(8.), (2, 2, 0.));
+    }
+
+    #[test]
+    fn parameters_at_sorted_ages_match_resampling() {
+        let trajectory = Trajectory::new(vec![line(1., 1.), line(3., 100.), line(7., 10.)]);
+        let ages = [0., 1., 2., 3., 5., 8.];
+        let times: Vec<Time> = ages.iter().map(|&age| Time::new::<year>(age)).collect();
+        assert_eq!(
+            trajectory.parameters_at_ages(&times),
+            trajectory.resample(&ages)
+        );
+    }
 }
@@ -1,13 +1,13 @@
 use astro_units::mass::solar_mass;
 use parsec_access::getters::{
-    get_closest_parameters, get_masses_in_solar, get_metallicities_in_mass_fractions,
-    get_trajectory, is_data_ready,
+    get_closest_parameters, get_initial_mass, get_lifetime, get_masses_in_solar,
+    get_metallicities_in_mass_fractions, get_trajectory, is_data_ready, masses, metallicities,
 };
 use uom::{
     fmt::DisplayStyle,
     si::{
         f64::{Length, Mass, ThermodynamicTemperature, Time},
-        length::kilometer,
+        length::{astronomical_unit, kilometer},
         thermodynamic_temperature::kelvin,
         time::year,
     },
@@ -47,6 +47,38 @@ fn recreating_the_sun() {
     );
 }
 
+#[test]
+fn sun_has_solar_density() {
+    assert!(is_data_ready());
+    let sun_metallicity = 0.0122;
+    let sun_mass = Mass::new::<solar_mass>(1.);
+    let sun_age = Time::new::<year>(4.6e9);
+    let params = get_closest_parameters(sun_metallicity, sun_mass, sun_age);
+    let density = params.density_in_solar();
+    assert!(
+        (density - 1.).abs() < 0.5,
+        "Expected density of 1 sol, got {}",
+        density
+    );
+}
+
+#[test]
+fn sun_has_earth_in_habitable_zone() {
+    assert!(is_data_ready());
+    let sun_metallicity = 0.0122;
+    let sun_mass = Mass::new::<solar_mass>(1.);
+    let sun_age = Time::new::<year>(4.6e9);
+    let params = get_closest_parameters(sun_metallicity, sun_mass, sun_age);
+    let (inner, outer) = params.habitable_zone();
+    let earth_distance = Length::new::<astronomical_unit>(1.);
+    assert!(
+        inner < earth_distance && earth_distance < outer,
+        "Expected 1 AU to lie between {} and {}",
+        inner.get::<astronomical_unit>(),
+        outer.get::<astronomical_unit>()
+    );
+}
+
 #[test]
 fn lifetime_mostly_decreases_with_mass() {
     assert!(is_data_ready());
@@ -72,6 +104,24 @@ fn lifetime_mostly_decreases_with_mass() {
     }
 }
 
+#[test]
+fn lifetime_getter_matches_trajectory() {
+    assert!(is_data_ready());
+    for (metallicity_index, _) in metallicities() {
+        for (mass_index, _) in masses(metallicity_index) {
+            let trajectory = get_trajectory(metallicity_index, mass_index);
+            assert_eq!(
+                get_lifetime(metallicity_index, mass_index),
+                trajectory.lifetime
+            );
+            assert_eq!(
+                get_initial_mass(metallicity_index, mass_index),
+                trajectory.initial_mass
+            );
+        }
+    }
+}
+
 #[test]
 fn bolometric_luminosity_fits_radius_and_temperature() {
     let sun_temperature = ThermodynamicTemperature::new::<kelvin>(5772.);
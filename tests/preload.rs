@@ -0,0 +1,20 @@
+#![cfg(feature = "test-data")]
+
+use parsec_access::{
+    config::{init, ParsecConfig, TrackSet},
+    getters::{is_loaded, num_metallicities},
+    preload::spawn_preload,
+};
+
+#[test]
+fn preloading_leaves_other_metallicities_unloaded() {
+    init(ParsecConfig::new().track_set(TrackSet::TestData))
+        .expect("the test data should be selected before loading");
+
+    let result = spawn_preload(vec![0]).join();
+    assert!(result.is_ok(), "{:?}", result.err());
+    assert!(is_loaded(0));
+    for metallicity_index in 1..num_metallicities() {
+        assert!(!is_loaded(metallicity_index));
+    }
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "test-data")]
+
+use astro_units::{mass::solar_mass, time::gigayear};
+use parsec_access::{
+    config::{init, ParsecConfig, TrackSet},
+    getters::{
+        export_reduced_data, get_closest_parameters, get_data, get_trajectory, is_data_ready,
+        masses, metallicities, num_metallicities, pending_downloads, reload_metallicity,
+    },
+    index::MetallicityIndex,
+    population::get_isochrone,
+};
+use uom::si::{
+    f64::{Mass, Time},
+    time::year,
+};
+
+fn use_test_data() {
+    init(
+        ParsecConfig::new()
+            .track_set(TrackSet::TestData)
+            .data_dir("this_folder_does_not_exist")
+            .offline(true),
+    )
+    .expect("the test data should be selected before loading");
+}
+
+#[test]
+fn test_data_is_ready_without_files() {
+    use_test_data();
+    assert!(pending_downloads().is_empty());
+    assert!(is_data_ready());
+    assert_eq!(num_metallicities(), 2);
+}
+
+#[test]
+fn test_data_contains_sun_like_star() {
+    use_test_data();
+    assert!(is_data_ready());
+    let sun_mass = Mass::new::<solar_mass>(1.);
+    let sun_age = Time::new::<year>(4.6e9);
+    let params = get_closest_parameters(0.0122, sun_mass, sun_age);
+    assert!(
+        (params.luminosity_in_solar - 1.).abs() < 0.15,
+        "Expected luminosity of 1 sol, got {}",
+        params.luminosity_in_solar,
+    );
+}
+
+#[test]
+fn test_data_lifetime_decreases_with_mass() {
+    use_test_data();
+    assert!(is_data_ready());
+    for (metallicity_index, _) in metallicities() {
+        for (mass_index, _) in masses(metallicity_index).skip(1) {
+            let lifetime = get_trajectory(metallicity_index, mass_index).lifetime;
+            let previous_lifetime = get_trajectory(metallicity_index, mass_index - 1).lifetime;
+            assert!(lifetime < previous_lifetime);
+        }
+    }
+}
+
+#[test]
+fn loaded_test_data_is_never_replaced() {
+    use_test_data();
+    assert!(is_data_ready());
+    let data = get_data(0);
+    assert!(reload_metallicity(0).is_err());
+    assert!(std::ptr::eq(data, get_data(0)));
+}
+
+#[test]
+fn grid_cannot_change_once_loaded() {
+    use_test_data();
+    assert!(is_data_ready());
+    assert!(init(ParsecConfig::new()).is_err());
+    assert_eq!(num_metallicities(), 2);
+}
+
+#[test]
+fn population_functions_accept_index_newtypes() {
+    use_test_data();
+    assert!(is_data_ready());
+    let age = Time::new::<gigayear>(1.);
+    assert_eq!(
+        get_isochrone(MetallicityIndex(1), age),
+        get_isochrone(1, age)
+    );
+}
+
+#[test]
+fn test_data_can_be_exported() {
+    use_test_data();
+    let dest = std::env::temp_dir().join("parsec_access_test_data_export");
+    assert!(export_reduced_data(&dest).is_ok());
+    let count = std::fs::read_dir(&dest).map(|entries| entries.count());
+    std::fs::remove_dir_all(&dest).expect("the export should be removable");
+    assert_eq!(count.ok(), Some(num_metallicities()));
+}